@@ -1,12 +1,111 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractevent, contractimpl, contracttype, token, Address, BytesN, Env, String, Vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, token, Address, BytesN,
+    Env, Map, String, Symbol, Val, Vec,
 };
 
 pub const ASSIGNMENT_TIMEOUT_SECONDS: u64 = 7 * 24 * 60 * 60;
+pub const MAX_PAGE_SIZE: u32 = 50;
+pub const INDEX_PAGE_SIZE: u32 = 50;
+pub const AUTO_RELEASE_SECONDS: u64 = 7 * 24 * 60 * 60;
+pub const MAX_MILESTONES: u32 = 20;
+pub const MAX_FEE_RECIPIENTS: u32 = 10;
+pub const EVIDENCE_WINDOW_SECONDS: u64 = 3 * 24 * 60 * 60;
+pub const MAX_EVIDENCE: u32 = 50;
+pub const MAX_PANEL_SIZE: u32 = 9;
+pub const MAX_REVISIONS: u32 = 3;
+pub const MAX_ADVANCE_BPS: u32 = 5000;
+
+/// Stable error codes returned by every fallible market entrypoint.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MarketError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    ContractPaused = 4,
+    ContractNotPaused = 5,
+    JobNotFound = 6,
+    NotJobOwner = 7,
+    JobNotOpen = 8,
+    JobNotAssigned = 9,
+    JobNotInProgress = 10,
+    JobNotPendingReview = 11,
+    JobNotDisputed = 12,
+    JobNotDisputable = 13,
+    JobFinalized = 14,
+    NotAssignedArtisan = 15,
+    NotJobParty = 16,
+    NotVerifiedArtisan = 17,
+    UserBlacklisted = 18,
+    NotCurator = 19,
+    NotAssignedJuror = 20,
+    DuplicateApplication = 21,
+    ArtisanAlreadyAssigned = 22,
+    AssignmentNotTimedOut = 23,
+    ReviewPeriodActive = 24,
+    FeeTooHigh = 25,
+    InvalidShares = 26,
+    Overflow = 27,
+    UserNotRegistered = 28,
+    RegistryCallFailed = 29,
+    InvalidInput = 30,
+    MilestoneNotFound = 31,
+    InvalidMilestoneStatus = 32,
+    MilestoneJob = 33,
+    NotMilestoneJob = 34,
+    DeadlineNotPassed = 35,
+    DeadlinePassed = 36,
+    ApplicationNotFound = 37,
+    TokenNotAllowed = 38,
+    AmountOutOfRange = 39,
+    JobNotCompleted = 40,
+    AlreadySubmitted = 41,
+    WindowClosed = 42,
+    LimitReached = 43,
+    RulingPending = 44,
+    NoRuling = 45,
+    OfferNotFound = 46,
+    RecurringJob = 47,
+    JobDisputed = 48,
+}
 
 mod registry {
-    use soroban_sdk::{contractclient, contracttype, Address, Env, String};
+    use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env, String};
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    #[repr(u32)]
+    pub enum RegistryError {
+        AlreadyInitialized = 1,
+        NotInitialized = 2,
+        Unauthorized = 3,
+        UserAlreadyRegistered = 4,
+        UserNotFound = 5,
+        CallerNotRegistered = 6,
+        AlreadyCurator = 7,
+        NotCurator = 8,
+        NotCuratorOrAdmin = 9,
+        MissingMetadata = 10,
+        ApplicationNotPending = 11,
+        AlreadyBlacklisted = 12,
+        NotBlacklisted = 13,
+        MarketNotAuthorized = 14,
+        InvalidRating = 15,
+    }
+
+    #[contracttype]
+    #[derive(Clone)]
+    pub struct ReputationStats {
+        pub completed_jobs: u32,
+        pub disputes_won: u32,
+        pub disputes_lost: u32,
+        pub jobs_abandoned: u32,
+        pub rating_count: u32,
+        pub rating_total: u64,
+        pub average_rating: u32,
+    }
 
     #[contracttype]
     #[derive(Clone)]
@@ -15,12 +114,29 @@ mod registry {
         pub metadata_hash: String,
         pub is_verified: bool,
         pub is_blacklisted: bool,
+        pub reputation: ReputationStats,
+    }
+
+    #[contracttype]
+    #[derive(Clone)]
+    pub enum ReputationUpdate {
+        JobCompleted,
+        DisputeWon,
+        DisputeLost,
+        JobAbandoned,
+        Rated(u32),
     }
 
     #[allow(dead_code)]
     #[contractclient(name = "Client")]
     pub trait RegistryTrait {
-        fn get_profile(env: &Env, user: Address) -> Profile;
+        fn get_profile(env: &Env, user: Address) -> Result<Profile, RegistryError>;
+        fn update_reputation(
+            env: &Env,
+            market: Address,
+            user: Address,
+            update: ReputationUpdate,
+        ) -> Result<(), RegistryError>;
     }
 }
 
@@ -34,6 +150,7 @@ pub enum JobStatus {
     Completed,
     Disputed,
     Cancelled,
+    Expired,
 }
 
 #[contracttype]
@@ -50,6 +167,55 @@ pub struct Job {
     pub end_time: u64,
     pub deadline: u64,
     pub dispute_reason: Option<String>,
+    /// Platform fee in basis points, fixed when the job is created.
+    pub fee_bps: u32,
+}
+
+/// `Job` as stored before `fee_bps` was added. Only read by `migrate_jobs`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyJob {
+    pub id: u64,
+    pub finder: Address,
+    pub artisan: Option<Address>,
+    pub juror: Option<Address>,
+    pub token: Address,
+    pub amount: i128,
+    pub status: JobStatus,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub deadline: u64,
+    pub dispute_reason: Option<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Released,
+    Disputed,
+    Resolved,
+    Cancelled,
+}
+
+/// Milestone terms supplied by the finder in `create_milestone_job`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneSpec {
+    pub description_hash: BytesN<32>,
+    pub amount: i128,
+    pub due_date: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub description_hash: BytesN<32>,
+    pub amount: i128,
+    pub due_date: u64,
+    pub status: MilestoneStatus,
+    pub submitted_at: u64,
 }
 
 #[contracttype]
@@ -58,6 +224,21 @@ pub struct JobApplicationRecord {
     pub job_id: u64,
     pub artisan: Address,
     pub applied_at: u64,
+    /// Price the artisan asks for the job, in the job's token.
+    pub quote: i128,
+    /// Estimated time to deliver, in seconds.
+    pub duration: u64,
+}
+
+/// A list of job ids kept for the listing views. Each index is stored as
+/// pages of at most `INDEX_PAGE_SIZE` ids plus each job's position, so no
+/// single entry grows with the number of jobs.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JobIndex {
+    Status(JobStatus),
+    Finder(Address),
+    Artisan(Address),
 }
 
 #[contracttype]
@@ -72,6 +253,188 @@ pub enum DataKey {
     AssignmentTime(u64),
     Application(u64, Address),
     JobApplicants(u64),
+    IndexLen(JobIndex),
+    IndexPage(JobIndex, u32),
+    IndexPos(JobIndex, u64),
+    Milestones(u64),
+    AllowedTokens,
+    FeeRecipients,
+    Review(u64, Address),
+    Reputation(Address),
+    Evidence(u64),
+    JurorAssignedAt(u64),
+    Panel(u64),
+    Votes(u64),
+    AppealConfig,
+    Ruling(u64),
+    JurorRewardBps,
+    DisputeTimeouts,
+    DisputedAt(u64),
+    Settlement(u64),
+    Cancellation(u64),
+    Revisions(u64),
+    TipFee,
+    Earnings(Address, Address),
+    Advance(u64),
+    Recurrence(u64),
+    ListingExpiry(u64),
+}
+
+/// Schedule of a recurring engagement. Each cycle is a milestone of equal
+/// amount, due one period after the previous one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurringTerms {
+    pub cycles: u32,
+    pub period_seconds: u64,
+    pub amount_per_cycle: i128,
+}
+
+/// An upfront share of a job's escrow paid to the artisan when the job
+/// starts. Once paid, `Job.amount` holds only the remaining escrow.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Advance {
+    pub advance_bps: u32,
+    pub paid: i128,
+}
+
+/// What an artisan has been paid in one token, net of fees. `total_tips` is
+/// the part of `total_earned` that came from tips.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Earnings {
+    pub payouts: u32,
+    pub total_earned: i128,
+    pub total_tips: i128,
+}
+
+/// A finder's request for changes after the artisan marked a job complete.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Revision {
+    pub round: u32,
+    pub notes_hash: BytesN<32>,
+    pub requested_at: u64,
+}
+
+/// A split of an assigned job's escrow between a refund to the finder and
+/// compensation for the artisan, which cancels the job once the other party
+/// accepts it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancellationOffer {
+    pub proposer: Address,
+    pub artisan: Address,
+    pub finder_refund: i128,
+    pub artisan_compensation: i128,
+    pub proposed_at: u64,
+}
+
+/// A split of the disputed escrow proposed by one party, which settles the
+/// dispute once the other party accepts it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementOffer {
+    pub proposer: Address,
+    pub finder_share: i128,
+    pub artisan_share: i128,
+    pub proposed_at: u64,
+}
+
+/// How long a dispute may wait for a panel to be seated and for the panel to
+/// rule before anyone can settle it with the default split. Zero disables the
+/// corresponding timeout.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DisputeTimeouts {
+    pub assignment_seconds: u64,
+    pub ruling_seconds: u64,
+    pub default_finder_bps: u32,
+}
+
+/// How long parties have to appeal a panel ruling, and the bond an appeal
+/// costs as a share of the disputed amount. A zero period pays rulings out
+/// immediately.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AppealConfig {
+    pub period_seconds: u64,
+    pub bond_bps: u32,
+}
+
+/// A panel's decided split, held until its appeal window closes or the
+/// appeal is decided.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ruling {
+    pub milestone: Option<u32>,
+    pub amount: i128,
+    pub finder_share: i128,
+    pub artisan_share: i128,
+    pub ruled_at: u64,
+    pub panel_size: u32,
+    pub appellant: Option<Address>,
+    pub bond: i128,
+    pub appealed_at: u64,
+}
+
+/// A panel member's proposed split of the disputed escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JurorVote {
+    pub juror: Address,
+    pub finder_share: i128,
+    pub artisan_share: i128,
+}
+
+/// One entry in a job's dispute evidence log.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Evidence {
+    pub submitted_by: Address,
+    pub evidence_hash: BytesN<32>,
+    pub submitted_at: u64,
+}
+
+/// A rating one party of a completed job left for the other.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Review {
+    pub job_id: u64,
+    pub reviewer: Address,
+    pub reviewee: Address,
+    pub rating: u32,
+    pub review_hash: BytesN<32>,
+    pub created_at: u64,
+}
+
+/// Ratings received by an address across all completed jobs.
+/// `average_rating` is in hundredths, e.g. 450 for 4.5 stars.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Reputation {
+    pub rating_count: u32,
+    pub rating_total: u64,
+    pub average_rating: u32,
+    pub last_updated: u64,
+}
+
+/// A platform fee destination and its share of every fee, in basis points.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeRecipient {
+    pub recipient: Address,
+    pub weight_bps: u32,
+}
+
+/// Job amount bounds for an allowlisted escrow token. `None` leaves that side
+/// unbounded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenLimits {
+    pub min_amount: Option<i128>,
+    pub max_amount: Option<i128>,
 }
 
 #[contractevent]
@@ -105,17 +468,74 @@ pub struct JobApplication {
     pub artisan: Address,
 }
 
+#[contractevent]
+pub struct ApplicationWithdrawn {
+    pub id: u64,
+    pub artisan: Address,
+}
+
+#[contractevent]
+pub struct ApplicationRejected {
+    pub id: u64,
+    pub artisan: Address,
+}
+
+#[contractevent]
+pub struct BidAccepted {
+    pub id: u64,
+    pub artisan: Address,
+    pub amount: i128,
+}
+
 #[contractevent]
 pub struct JobStarted {
     pub id: u64,
     pub artisan: Address,
 }
 
+#[contractevent]
+pub struct RevisionRequested {
+    pub id: u64,
+    pub round: u32,
+    pub notes_hash: BytesN<32>,
+}
+
+#[contractevent]
+pub struct AdvanceSet {
+    pub id: u64,
+    pub advance_bps: u32,
+}
+
+#[contractevent]
+pub struct AdvanceReleased {
+    pub id: u64,
+    pub artisan: Address,
+    pub amount: i128,
+}
+
 #[contractevent]
 pub struct JobCancelled {
     pub id: u64,
 }
 
+#[contractevent]
+pub struct JobExpired {
+    pub id: u64,
+    pub refunded: i128,
+}
+
+#[contractevent]
+pub struct AssignmentDeclined {
+    pub id: u64,
+    pub artisan: Address,
+}
+
+#[contractevent]
+pub struct JobAbandoned {
+    pub id: u64,
+    pub artisan: Address,
+}
+
 #[contractevent]
 pub struct JobCompleted {
     pub id: u64,
@@ -127,6 +547,7 @@ pub struct FundsReleased {
     pub id: u64,
     pub artisan: Address,
     pub amount: i128,
+    pub tip: i128,
 }
 
 #[contractevent]
@@ -135,11 +556,20 @@ pub struct DisputeRaised {
     pub raised_by: Address,
 }
 
+#[contractevent]
+pub struct EvidenceSubmitted {
+    pub id: u64,
+    pub submitted_by: Address,
+    pub evidence_hash: BytesN<32>,
+}
+
 #[contractevent]
 pub struct DisputeResolved {
     pub id: u64,
     pub finder_share: i128,
     pub artisan_share: i128,
+    pub platform_fee: i128,
+    pub juror_reward: i128,
 }
 
 #[contractevent]
@@ -149,6 +579,13 @@ pub struct DeadlineExtended {
     pub new_deadline: u64,
 }
 
+#[contractevent]
+pub struct DeadlineMissed {
+    pub id: u64,
+    pub artisan: Address,
+    pub deadline: u64,
+}
+
 #[contractevent]
 pub struct BudgetIncreased {
     pub id: u64,
@@ -185,835 +622,3754 @@ pub struct FeeUpdated {
 }
 
 #[contractevent]
-pub struct JurorAssigned {
-    pub id: u64,
-    pub juror: Address,
+pub struct TipFeeUpdated {
+    pub new_fee_bps: u32,
 }
 
-#[contract]
-pub struct MarketContract;
+#[contractevent]
+pub struct ReviewSubmitted {
+    pub id: u64,
+    pub reviewer: Address,
+    pub reviewee: Address,
+    pub rating: u32,
+}
 
-pub fn is_paused(env: &Env) -> bool {
-    let paused = env
-        .storage()
-        .instance()
-        .get(&DataKey::IsPaused)
-        .expect("Missing storage variable");
-    env.storage().instance().extend_ttl(100_000, 500_000);
-    paused
+#[contractevent]
+pub struct FeeRecipientsUpdated {
+    pub recipients: Vec<FeeRecipient>,
 }
 
-#[contractimpl]
-impl MarketContract {
-    pub fn initialize(env: Env, registry_contract: Address, admin: &Address) {
-        if env.storage().instance().has(&DataKey::RegistryContract) {
-            panic!("Registry already initialized");
-        }
-        if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Admin already initialized");
-        }
-        env.storage()
-            .instance()
-            .set(&DataKey::RegistryContract, &registry_contract);
-        env.storage().instance().set(&DataKey::Admin, admin);
-        env.storage().instance().set(&DataKey::IsPaused, &false);
-    }
+#[contractevent]
+pub struct TokenAllowed {
+    pub token: Address,
+    pub min_amount: Option<i128>,
+    pub max_amount: Option<i128>,
+}
 
-    pub fn create_job(env: Env, finder: Address, token: Address, amount: i128) -> u64 {
-        assert!(!is_paused(&env), "Contract Paused");
-        finder.require_auth();
+#[contractevent]
+pub struct TokenRemoved {
+    pub token: Address,
+}
 
-        let token_client = token::TokenClient::new(&env, &token);
-        token_client.transfer(&finder, env.current_contract_address(), &amount);
+#[contractevent]
+pub struct JurorAssigned {
+    pub id: u64,
+    pub juror: Address,
+}
 
-        let counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::JobCounter)
-            .unwrap_or(0);
-        let id = counter + 1;
-        env.storage().instance().set(&DataKey::JobCounter, &id);
-        env.storage().instance().extend_ttl(100_000, 500_000);
+#[contractevent]
+pub struct PanelAssigned {
+    pub id: u64,
+    pub jurors: Vec<Address>,
+}
 
-        let job = Job {
-            id,
-            finder,
-            artisan: None,
-            juror: None,
-            token,
-            amount,
-            status: JobStatus::Open,
-            start_time: 0,
-            end_time: 0,
-            deadline: 0,
-            dispute_reason: None,
-        };
-        env.storage().persistent().set(&DataKey::Job(id), &job);
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(id), 100_000, 500_000);
+#[contractevent]
+pub struct JurorVoted {
+    pub id: u64,
+    pub juror: Address,
+    pub finder_share: i128,
+    pub artisan_share: i128,
+}
 
-        JobCreated { id, amount }.publish(&env);
+#[contractevent]
+pub struct MilestoneSubmitted {
+    pub id: u64,
+    pub index: u32,
+}
 
-        id
-    }
+#[contractevent]
+pub struct MilestoneReleased {
+    pub id: u64,
+    pub index: u32,
+    pub artisan: Address,
+    pub amount: i128,
+}
 
-    pub fn assign_artisan(env: Env, finder: Address, job_id: u64, artisan: Address) {
-        assert!(!is_paused(&env), "Contract Paused");
-        let registry_contract: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::RegistryContract)
-            .expect("Contract not initialized");
+#[contractevent]
+pub struct RecurringJobCreated {
+    pub id: u64,
+    pub artisan: Address,
+    pub cycles: u32,
+    pub period_seconds: u64,
+}
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+#[contractevent]
+pub struct RecurringJobTerminated {
+    pub id: u64,
+    pub terminated_by: Address,
+    pub refunded: i128,
+}
 
-        finder.require_auth();
+#[contractevent]
+pub struct MilestoneDisputed {
+    pub id: u64,
+    pub index: u32,
+    pub raised_by: Address,
+}
 
-        if job.finder != finder {
-            panic!("Not job owner");
-        }
+#[contractevent]
+pub struct MilestoneDisputeResolved {
+    pub id: u64,
+    pub index: u32,
+    pub finder_share: i128,
+    pub artisan_share: i128,
+    pub platform_fee: i128,
+    pub juror_reward: i128,
+}
 
-        if job.status != JobStatus::Open {
-            panic!("Job is not open");
-        }
+#[contractevent]
+pub struct RulingIssued {
+    pub id: u64,
+    pub finder_share: i128,
+    pub artisan_share: i128,
+    pub appeal_deadline: u64,
+}
+
+#[contractevent]
+pub struct RulingAppealed {
+    pub id: u64,
+    pub appellant: Address,
+    pub bond: i128,
+}
+
+#[contractevent]
+pub struct AppealDecided {
+    pub id: u64,
+    pub appellant: Address,
+    pub bond_refunded: bool,
+}
+
+#[contractevent]
+pub struct DisputeTimeoutsUpdated {
+    pub assignment_seconds: u64,
+    pub ruling_seconds: u64,
+    pub default_finder_bps: u32,
+}
+
+#[contractevent]
+pub struct DisputeExpired {
+    pub id: u64,
+    pub finder_share: i128,
+    pub artisan_share: i128,
+}
+
+#[contractevent]
+pub struct SettlementProposed {
+    pub id: u64,
+    pub proposer: Address,
+    pub finder_share: i128,
+    pub artisan_share: i128,
+}
+
+#[contractevent]
+pub struct SettlementAccepted {
+    pub id: u64,
+    pub accepted_by: Address,
+}
+
+#[contractevent]
+pub struct CancellationProposed {
+    pub id: u64,
+    pub proposer: Address,
+    pub finder_refund: i128,
+    pub artisan_compensation: i128,
+}
+
+#[contractevent]
+pub struct JobMutuallyCancelled {
+    pub id: u64,
+    pub finder_refund: i128,
+    pub artisan_compensation: i128,
+}
+
+#[contractevent]
+pub struct JurorRewardUpdated {
+    pub share_bps: u32,
+}
+
+#[contractevent]
+pub struct AppealConfigUpdated {
+    pub period_seconds: u64,
+    pub bond_bps: u32,
+}
+
+#[contract]
+pub struct MarketContract;
+
+pub fn is_paused(env: &Env) -> Result<bool, MarketError> {
+    let paused = env
+        .storage()
+        .instance()
+        .get(&DataKey::IsPaused)
+        .ok_or(MarketError::NotInitialized)?;
+    env.storage().instance().extend_ttl(100_000, 500_000);
+    Ok(paused)
+}
+
+fn require_not_paused(env: &Env) -> Result<(), MarketError> {
+    if is_paused(env)? {
+        return Err(MarketError::ContractPaused);
+    }
+    Ok(())
+}
+
+fn read_admin(env: &Env) -> Result<Address, MarketError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(MarketError::NotInitialized)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), MarketError> {
+    admin.require_auth();
+
+    let current_admin = read_admin(env)?;
+    if *admin != current_admin {
+        return Err(MarketError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn read_registry(env: &Env) -> Result<Address, MarketError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::RegistryContract)
+        .ok_or(MarketError::NotInitialized)
+}
+
+fn read_fee_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::PlatformFee)
+        .unwrap_or(100)
+}
+
+fn read_allowed_tokens(env: &Env) -> Map<Address, TokenLimits> {
+    env.storage()
+        .instance()
+        .get(&DataKey::AllowedTokens)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Checks that `token` is allowlisted and `amount` is within its job limits.
+fn require_allowed_amount(env: &Env, token: &Address, amount: i128) -> Result<(), MarketError> {
+    let limits = read_allowed_tokens(env)
+        .get(token.clone())
+        .ok_or(MarketError::TokenNotAllowed)?;
+    if let Some(min_amount) = limits.min_amount {
+        if amount < min_amount {
+            return Err(MarketError::AmountOutOfRange);
+        }
+    }
+    if let Some(max_amount) = limits.max_amount {
+        if amount > max_amount {
+            return Err(MarketError::AmountOutOfRange);
+        }
+    }
+    Ok(())
+}
+
+fn platform_fee(job: &Job, amount: i128) -> i128 {
+    (amount * (job.fee_bps as i128)) / 10000
+}
+
+/// Returns the fee recipients. Contracts initialized before fee recipients
+/// existed have none until the admin calls `set_fee_recipients`, and fees
+/// cannot be paid out until then.
+fn read_fee_recipients(env: &Env) -> Result<Vec<FeeRecipient>, MarketError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::FeeRecipients)
+        .ok_or(MarketError::NotInitialized)
+}
+
+/// Splits `fee` held in escrow between the fee recipients by weight. Rounding
+/// dust goes to the last recipient.
+fn pay_platform_fee(env: &Env, token: &Address, fee: i128) -> Result<(), MarketError> {
+    if fee <= 0 {
+        return Ok(());
+    }
+    let recipients = read_fee_recipients(env)?;
+    let token_client = token::TokenClient::new(env, token);
+    let contract = env.current_contract_address();
+
+    let mut remaining = fee;
+    let last = recipients.len() - 1;
+    for (i, entry) in recipients.iter().enumerate() {
+        let share = if i as u32 == last {
+            remaining
+        } else {
+            (fee * (entry.weight_bps as i128)) / 10000
+        };
+        if share > 0 {
+            token_client.transfer(&contract, &entry.recipient, &share);
+        }
+        remaining -= share;
+    }
+    Ok(())
+}
+
+/// Pays `amount` of a job's escrow to its artisan, sending the platform fee to
+/// the fee recipients. Returns the artisan's net payout.
+fn release_to_artisan(
+    env: &Env,
+    job: &Job,
+    artisan: &Address,
+    amount: i128,
+) -> Result<i128, MarketError> {
+    let fee = platform_fee(job, amount);
+    let payout = amount - fee;
+
+    let token_client = token::TokenClient::new(env, &job.token);
+    token_client.transfer(&env.current_contract_address(), artisan, &payout);
+    pay_platform_fee(env, &job.token, fee)?;
+    record_earnings(env, artisan, &job.token, payout, 0);
+    Ok(payout)
+}
+
+/// Pays a finder's tip to the artisan, less the tip fee. Returns the
+/// artisan's net tip.
+fn pay_tip(env: &Env, job: &Job, artisan: &Address, tip: i128) -> Result<i128, MarketError> {
+    let fee_bps: u32 = env.storage().instance().get(&DataKey::TipFee).unwrap_or(0);
+    let fee = (tip * (fee_bps as i128)) / 10000;
+    let net = tip - fee;
+
+    let token_client = token::TokenClient::new(env, &job.token);
+    let contract = env.current_contract_address();
+    token_client.transfer(&job.finder, &contract, &tip);
+    token_client.transfer(&contract, artisan, &net);
+    pay_platform_fee(env, &job.token, fee)?;
+    record_earnings(env, artisan, &job.token, net, net);
+    Ok(net)
+}
+
+fn record_earnings(env: &Env, artisan: &Address, token: &Address, amount: i128, tip: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let key = DataKey::Earnings(artisan.clone(), token.clone());
+    let mut earnings: Earnings = env.storage().persistent().get(&key).unwrap_or_default();
+    earnings.payouts = earnings.payouts.saturating_add(1);
+    earnings.total_earned = earnings.total_earned.saturating_add(amount);
+    earnings.total_tips = earnings.total_tips.saturating_add(tip);
+    env.storage().persistent().set(&key, &earnings);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
+/// Checks that the shares account for the whole `amount` net of fee.
+fn validate_split(
+    job: &Job,
+    amount: i128,
+    finder_share: i128,
+    artisan_share: i128,
+) -> Result<(), MarketError> {
+    let fee = platform_fee(job, amount);
+    if finder_share < 0 || artisan_share < 0 || finder_share + artisan_share + fee != amount {
+        return Err(MarketError::InvalidShares);
+    }
+    Ok(())
+}
+
+fn read_juror_reward_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::JurorRewardBps)
+        .unwrap_or(0)
+}
+
+/// Pays the jurors who voted on a dispute their share of `fee`, split evenly.
+/// Returns the amount paid; rounding dust stays with the platform.
+fn pay_juror_reward(env: &Env, job: &Job, fee: i128) -> i128 {
+    let votes: Vec<JurorVote> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Votes(job.id))
+        .unwrap_or_else(|| Vec::new(env));
+    if votes.is_empty() {
+        return 0;
+    }
+
+    let reward = (fee * (read_juror_reward_bps(env) as i128)) / 10000;
+    let per_juror = reward / (votes.len() as i128);
+    if per_juror <= 0 {
+        return 0;
+    }
+
+    let token_client = token::TokenClient::new(env, &job.token);
+    let contract = env.current_contract_address();
+    for vote in votes.iter() {
+        token_client.transfer(&contract, &vote.juror, &per_juror);
+    }
+    per_juror * (votes.len() as i128)
+}
+
+/// Splits `amount` of disputed escrow between the parties after deducting the
+/// platform fee, of which the voting jurors receive the configured reward and
+/// the fee recipients the rest. The shares must account for the whole amount
+/// net of fee. Returns the platform's and the jurors' cut of the fee.
+fn pay_dispute_split(
+    env: &Env,
+    job: &Job,
+    amount: i128,
+    finder_share: i128,
+    artisan_share: i128,
+) -> Result<(i128, i128), MarketError> {
+    validate_split(job, amount, finder_share, artisan_share)?;
+    let fee = platform_fee(job, amount);
+
+    let token_client = token::TokenClient::new(env, &job.token);
+    let contract = env.current_contract_address();
+
+    if finder_share > 0 {
+        token_client.transfer(&contract, &job.finder, &finder_share);
+    }
+
+    if artisan_share > 0 {
+        let artisan = job.artisan.clone().ok_or(MarketError::JobNotAssigned)?;
+        token_client.transfer(&contract, &artisan, &artisan_share);
+        record_earnings(env, &artisan, &job.token, artisan_share, 0);
+    }
+
+    let juror_reward = pay_juror_reward(env, job, fee);
+    let platform_fee = fee - juror_reward;
+    pay_platform_fee(env, &job.token, platform_fee)?;
+    Ok((platform_fee, juror_reward))
+}
+
+fn read_recurrence(env: &Env, job_id: u64) -> Option<RecurringTerms> {
+    env.storage().persistent().get(&DataKey::Recurrence(job_id))
+}
+
+/// When cycle `index` of a recurring job opens, one period before it is due.
+fn cycle_start(terms: &RecurringTerms, milestone: &Milestone) -> u64 {
+    milestone.due_date.saturating_sub(terms.period_seconds)
+}
+
+fn read_job(env: &Env, job_id: u64) -> Result<Job, MarketError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Job(job_id))
+        .ok_or(MarketError::JobNotFound)
+}
+
+fn write_job(env: &Env, job: &Job) {
+    let key = DataKey::Job(job.id);
+    env.storage().persistent().set(&key, job);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
+/// Pulls `amount` of `token` from the finder into escrow and stores a new
+/// `Open` job for it. A `deadline` of 0 means the job has no deadline.
+fn escrow_new_job(
+    env: &Env,
+    finder: Address,
+    token: Address,
+    amount: i128,
+    deadline: u64,
+) -> Result<u64, MarketError> {
+    if deadline != 0 && deadline <= env.ledger().timestamp() {
+        return Err(MarketError::InvalidInput);
+    }
+    require_allowed_amount(env, &token, amount)?;
+
+    let token_client = token::TokenClient::new(env, &token);
+    token_client.transfer(&finder, env.current_contract_address(), &amount);
+
+    let counter: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::JobCounter)
+        .unwrap_or(0);
+    let id = counter + 1;
+    env.storage().instance().set(&DataKey::JobCounter, &id);
+    env.storage().instance().extend_ttl(100_000, 500_000);
+
+    let job = Job {
+        id,
+        finder,
+        artisan: None,
+        juror: None,
+        token,
+        amount,
+        status: JobStatus::Open,
+        start_time: 0,
+        end_time: 0,
+        deadline,
+        dispute_reason: None,
+        fee_bps: read_fee_bps(env),
+    };
+    write_job(env, &job);
+    index_insert(env, &JobIndex::Status(JobStatus::Open), id);
+    index_insert(env, &JobIndex::Finder(job.finder.clone()), id);
+
+    JobCreated { id, amount }.publish(env);
+
+    Ok(id)
+}
+
+fn read_listing_expiry(env: &Env, job_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ListingExpiry(job_id))
+        .unwrap_or(0)
+}
+
+/// Whether an open job's listing has passed its expiry. Listings without one
+/// never expire.
+fn listing_expired(env: &Env, job_id: u64) -> bool {
+    let expires_at = read_listing_expiry(env, job_id);
+    expires_at != 0 && env.ledger().timestamp() > expires_at
+}
+
+fn read_milestones(env: &Env, job_id: u64) -> Option<Vec<Milestone>> {
+    let key = DataKey::Milestones(job_id);
+    let milestones = env.storage().persistent().get(&key);
+    if milestones.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+    }
+    milestones
+}
+
+fn write_milestones(env: &Env, job_id: u64, milestones: &Vec<Milestone>) {
+    let key = DataKey::Milestones(job_id);
+    env.storage().persistent().set(&key, milestones);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
+fn require_single_payment_job(env: &Env, job_id: u64) -> Result<(), MarketError> {
+    if env.storage().persistent().has(&DataKey::Milestones(job_id)) {
+        return Err(MarketError::MilestoneJob);
+    }
+    Ok(())
+}
+
+/// A recurring job is scheduled with one artisan, so it is ended with
+/// `terminate_recurring_job` rather than reopened or handed to another.
+fn require_non_recurring_job(env: &Env, job_id: u64) -> Result<(), MarketError> {
+    if env.storage().persistent().has(&DataKey::Recurrence(job_id)) {
+        return Err(MarketError::RecurringJob);
+    }
+    Ok(())
+}
+
+/// Completes a milestone job once every milestone has been paid out or
+/// cancelled.
+fn settle_if_all_milestones_done(env: &Env, job: &mut Job, milestones: &Vec<Milestone>) {
+    let all_done = milestones.iter().all(|milestone| {
+        milestone.status == MilestoneStatus::Released
+            || milestone.status == MilestoneStatus::Resolved
+            || milestone.status == MilestoneStatus::Cancelled
+    });
+    if all_done {
+        set_job_status(env, job, JobStatus::Completed);
+        report_completion(env, job);
+    }
+}
+
+fn index_len(env: &Env, index: &JobIndex) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexLen(index.clone()))
+        .unwrap_or(0)
+}
+
+fn write_index_len(env: &Env, index: &JobIndex, len: u32) {
+    let key = DataKey::IndexLen(index.clone());
+    env.storage().persistent().set(&key, &len);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
+fn read_index_page(env: &Env, index: &JobIndex, page: u32) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexPage(index.clone(), page))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn write_index_page(env: &Env, index: &JobIndex, page: u32, ids: &Vec<u64>) {
+    let key = DataKey::IndexPage(index.clone(), page);
+    if ids.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, ids);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
+fn write_index_pos(env: &Env, index: &JobIndex, job_id: u64, pos: u32) {
+    let key = DataKey::IndexPos(index.clone(), job_id);
+    env.storage().persistent().set(&key, &pos);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
+/// Appends a job to an index. Returns false if it was already listed.
+fn index_insert(env: &Env, index: &JobIndex, job_id: u64) -> bool {
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::IndexPos(index.clone(), job_id))
+    {
+        return false;
+    }
+
+    let len = index_len(env, index);
+    let page = len / INDEX_PAGE_SIZE;
+    let mut ids = read_index_page(env, index, page);
+    ids.push_back(job_id);
+    write_index_page(env, index, page, &ids);
+    write_index_pos(env, index, job_id, len);
+    write_index_len(env, index, len + 1);
+    true
+}
+
+/// Removes a job from an index by moving the last entry into its slot.
+fn index_remove(env: &Env, index: &JobIndex, job_id: u64) {
+    let pos_key = DataKey::IndexPos(index.clone(), job_id);
+    let pos: u32 = match env.storage().persistent().get(&pos_key) {
+        Some(pos) => pos,
+        None => return,
+    };
+    env.storage().persistent().remove(&pos_key);
+
+    let last = index_len(env, index) - 1;
+    let last_page = last / INDEX_PAGE_SIZE;
+    let mut last_ids = read_index_page(env, index, last_page);
+    let moved_id = last_ids.pop_back();
+    if pos != last {
+        if let Some(moved_id) = moved_id {
+            let page = pos / INDEX_PAGE_SIZE;
+            if page == last_page {
+                last_ids.set(pos % INDEX_PAGE_SIZE, moved_id);
+            } else {
+                let mut ids = read_index_page(env, index, page);
+                ids.set(pos % INDEX_PAGE_SIZE, moved_id);
+                write_index_page(env, index, page, &ids);
+            }
+            write_index_pos(env, index, moved_id, pos);
+        }
+    }
+    write_index_page(env, index, last_page, &last_ids);
+    write_index_len(env, index, last);
+}
+
+/// Moves a job to `status`, keeping the per-status index in sync. A pending
+/// cancellation proposal was made for the old status and is dropped.
+fn set_job_status(env: &Env, job: &mut Job, status: JobStatus) {
+    index_remove(env, &JobIndex::Status(job.status.clone()), job.id);
+    index_insert(env, &JobIndex::Status(status.clone()), job.id);
+    job.status = status;
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Cancellation(job.id));
+}
+
+fn read_dispute_timeouts(env: &Env) -> DisputeTimeouts {
+    env.storage()
+        .instance()
+        .get(&DataKey::DisputeTimeouts)
+        .unwrap_or_default()
+}
+
+/// Moves a job into `Disputed` and starts the dispute timeout clock.
+fn open_dispute(env: &Env, job: &mut Job) {
+    set_job_status(env, job, JobStatus::Disputed);
+
+    let key = DataKey::DisputedAt(job.id);
+    env.storage()
+        .persistent()
+        .set(&key, &env.ledger().timestamp());
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
+/// Returns up to `limit` jobs from an index, starting at position `cursor`.
+fn read_job_page(env: &Env, index: &JobIndex, cursor: u64, limit: u32) -> Vec<Job> {
+    let mut jobs = Vec::new(env);
+    let len = index_len(env, index);
+    if cursor >= len as u64 {
+        return jobs;
+    }
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::IndexLen(index.clone()), 100_000, 500_000);
+
+    let start = cursor as u32;
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len);
+    let mut page = start / INDEX_PAGE_SIZE;
+    let mut ids = read_index_page(env, index, page);
+    for pos in start..end {
+        if pos / INDEX_PAGE_SIZE != page {
+            page = pos / INDEX_PAGE_SIZE;
+            ids = read_index_page(env, index, page);
+        }
+        if let Some(job_id) = ids.get(pos % INDEX_PAGE_SIZE) {
+            if let Ok(job) = read_job(env, job_id) {
+                jobs.push_back(job);
+            }
+        }
+    }
+    jobs
+}
+
+fn read_profile(env: &Env, user: &Address) -> Result<registry::Profile, MarketError> {
+    let registry_contract = read_registry(env)?;
+    let registry_client = registry::Client::new(env, &registry_contract);
+    match registry_client.try_get_profile(user) {
+        Ok(Ok(profile)) => Ok(profile),
+        Err(Ok(registry::RegistryError::UserNotFound)) => Err(MarketError::UserNotRegistered),
+        _ => Err(MarketError::RegistryCallFailed),
+    }
+}
+
+/// Reports a job outcome to the registry. This is best effort: payouts must
+/// not depend on the user being registered or this market being authorised.
+fn report_reputation(env: &Env, user: &Address, update: registry::ReputationUpdate) {
+    if let Ok(registry_contract) = read_registry(env) {
+        let registry_client = registry::Client::new(env, &registry_contract);
+        let _ =
+            registry_client.try_update_reputation(&env.current_contract_address(), user, &update);
+    }
+}
+
+fn report_completion(env: &Env, job: &Job) {
+    report_reputation(env, &job.finder, registry::ReputationUpdate::JobCompleted);
+    if let Some(artisan) = &job.artisan {
+        report_reputation(env, artisan, registry::ReputationUpdate::JobCompleted);
+    }
+}
+
+/// Reports the party awarded the larger share as the dispute winner. An even
+/// split counts as neither a win nor a loss.
+fn report_dispute_outcome(env: &Env, job: &Job, finder_share: i128, artisan_share: i128) {
+    let artisan = match &job.artisan {
+        Some(artisan) => artisan,
+        None => return,
+    };
+    let (winner, loser) = if artisan_share > finder_share {
+        (artisan, &job.finder)
+    } else if finder_share > artisan_share {
+        (&job.finder, artisan)
+    } else {
+        return;
+    };
+    report_reputation(env, winner, registry::ReputationUpdate::DisputeWon);
+    report_reputation(env, loser, registry::ReputationUpdate::DisputeLost);
+}
+
+fn require_artisan_profile(env: &Env, artisan: &Address) -> Result<(), MarketError> {
+    let profile = read_profile(env, artisan)?;
+
+    if profile.role != 3 {
+        return Err(MarketError::NotVerifiedArtisan);
+    }
+    if profile.is_blacklisted {
+        return Err(MarketError::UserBlacklisted);
+    }
+    Ok(())
+}
+
+/// Hands an `Open` job to `artisan` and starts the assignment timeout.
+fn assign_job(env: &Env, job: &mut Job, artisan: Address) {
+    job.artisan = Some(artisan.clone());
+    set_job_status(env, job, JobStatus::Assigned);
+
+    write_job(env, job);
+    index_insert(env, &JobIndex::Artisan(artisan.clone()), job.id);
+    let key = DataKey::AssignmentTime(job.id);
+    env.storage()
+        .persistent()
+        .set(&key, &env.ledger().timestamp());
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+
+    JobAssigned {
+        id: job.id,
+        artisan,
+    }
+    .publish(env);
+}
+
+/// Deletes an artisan's application record and drops them from the job's
+/// applicant list.
+fn remove_application(env: &Env, job_id: u64, artisan: &Address) -> Result<(), MarketError> {
+    let app_key = DataKey::Application(job_id, artisan.clone());
+    if !env.storage().persistent().has(&app_key) {
+        return Err(MarketError::ApplicationNotFound);
+    }
+    env.storage().persistent().remove(&app_key);
+
+    let applicants_key = DataKey::JobApplicants(job_id);
+    let applicants: Option<Vec<Address>> = env.storage().persistent().get(&applicants_key);
+    if let Some(mut applicants) = applicants {
+        if let Some(pos) = applicants.first_index_of(artisan) {
+            applicants.remove(pos);
+            env.storage().persistent().set(&applicants_key, &applicants);
+            env.storage()
+                .persistent()
+                .extend_ttl(&applicants_key, 100_000, 500_000);
+        }
+    }
+    Ok(())
+}
+
+fn read_reputation(env: &Env, user: &Address) -> Reputation {
+    let key = DataKey::Reputation(user.clone());
+    let reputation = env.storage().persistent().get(&key);
+    if reputation.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+    }
+    reputation.unwrap_or_default()
+}
+
+fn add_rating(env: &Env, user: &Address, rating: u32) -> Result<(), MarketError> {
+    let mut reputation = read_reputation(env, user);
+    reputation.rating_count = reputation
+        .rating_count
+        .checked_add(1)
+        .ok_or(MarketError::Overflow)?;
+    reputation.rating_total = reputation
+        .rating_total
+        .checked_add(rating as u64)
+        .ok_or(MarketError::Overflow)?;
+    reputation.average_rating =
+        ((reputation.rating_total * 100) / reputation.rating_count as u64) as u32;
+    reputation.last_updated = env.ledger().timestamp();
+
+    let key = DataKey::Reputation(user.clone());
+    env.storage().persistent().set(&key, &reputation);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+    Ok(())
+}
+
+/// Returns the job's panel. A juror seated before panels existed has no
+/// `Panel` entry and sits as a one-member panel.
+fn read_panel(env: &Env, job: &Job) -> Vec<Address> {
+    let panel: Option<Vec<Address>> = env.storage().persistent().get(&DataKey::Panel(job.id));
+    match (panel, &job.juror) {
+        (Some(panel), _) => panel,
+        (None, Some(juror)) => Vec::from_array(env, [juror.clone()]),
+        (None, None) => Vec::new(env),
+    }
+}
+
+/// Stores `jurors` as the job's panel, with the first as `Job.juror`, and
+/// opens the evidence window. After an appeal the new panel must be larger
+/// than the one that issued the ruling.
+fn seat_panel(env: &Env, job: &mut Job, jurors: &Vec<Address>) -> Result<(), MarketError> {
+    if let Some(ruling) = read_ruling(env, job.id) {
+        if ruling.appellant.is_none() {
+            return Err(MarketError::RulingPending);
+        }
+        if jurors.len() <= ruling.panel_size {
+            return Err(MarketError::InvalidInput);
+        }
+    }
+
+    job.juror = jurors.first();
+    write_job(env, job);
+
+    let panel_key = DataKey::Panel(job.id);
+    env.storage().persistent().set(&panel_key, jurors);
+    env.storage()
+        .persistent()
+        .extend_ttl(&panel_key, 100_000, 500_000);
+    env.storage().persistent().remove(&DataKey::Votes(job.id));
+
+    let assigned_key = DataKey::JurorAssignedAt(job.id);
+    env.storage()
+        .persistent()
+        .set(&assigned_key, &env.ledger().timestamp());
+    env.storage()
+        .persistent()
+        .extend_ttl(&assigned_key, 100_000, 500_000);
+    Ok(())
+}
+
+fn clear_panel(env: &Env, job: &mut Job) {
+    job.juror = None;
+    env.storage().persistent().remove(&DataKey::Panel(job.id));
+    env.storage().persistent().remove(&DataKey::Votes(job.id));
+}
+
+/// Records `juror`'s proposed split of `amount`. Returns the panel's final
+/// split once it is decided: as soon as a majority of the panel proposes the
+/// same split, or otherwise the median finder share once every juror has
+/// voted.
+fn cast_vote(
+    env: &Env,
+    job: &Job,
+    juror: &Address,
+    amount: i128,
+    finder_share: i128,
+    artisan_share: i128,
+) -> Result<Option<(i128, i128)>, MarketError> {
+    if let Some(ruling) = read_ruling(env, job.id) {
+        if ruling.appellant.is_none() {
+            return Err(MarketError::RulingPending);
+        }
+    }
+    let panel = read_panel(env, job);
+    if !panel.contains(juror) {
+        return Err(MarketError::NotAssignedJuror);
+    }
+    validate_split(job, amount, finder_share, artisan_share)?;
+
+    let key = DataKey::Votes(job.id);
+    let mut votes: Vec<JurorVote> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    if votes.iter().any(|vote| vote.juror == *juror) {
+        return Err(MarketError::AlreadySubmitted);
+    }
+    votes.push_back(JurorVote {
+        juror: juror.clone(),
+        finder_share,
+        artisan_share,
+    });
+    env.storage().persistent().set(&key, &votes);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+
+    JurorVoted {
+        id: job.id,
+        juror: juror.clone(),
+        finder_share,
+        artisan_share,
+    }
+    .publish(env);
+
+    let quorum = panel.len() / 2 + 1;
+    let matching = votes
+        .iter()
+        .filter(|vote| vote.finder_share == finder_share)
+        .count() as u32;
+    if matching >= quorum {
+        return Ok(Some((finder_share, artisan_share)));
+    }
+    if votes.len() < panel.len() {
+        return Ok(None);
+    }
+
+    // Every juror has voted without a majority: take the median finder share.
+    let mut shares: Vec<i128> = Vec::new(env);
+    for vote in votes.iter() {
+        let pos = match shares.binary_search(vote.finder_share) {
+            Ok(pos) | Err(pos) => pos,
+        };
+        shares.insert(pos, vote.finder_share);
+    }
+    let median = shares
+        .get(shares.len() / 2)
+        .ok_or(MarketError::InvalidInput)?;
+    let net = amount - platform_fee(job, amount);
+    Ok(Some((median, net - median)))
+}
+
+fn read_appeal_config(env: &Env) -> AppealConfig {
+    env.storage()
+        .instance()
+        .get(&DataKey::AppealConfig)
+        .unwrap_or_default()
+}
+
+fn read_ruling(env: &Env, job_id: u64) -> Option<Ruling> {
+    env.storage().persistent().get(&DataKey::Ruling(job_id))
+}
+
+fn write_ruling(env: &Env, job_id: u64, ruling: &Ruling) {
+    let key = DataKey::Ruling(job_id);
+    env.storage().persistent().set(&key, ruling);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
+/// Returns the disputed milestone, if any, and the escrow amount in dispute.
+fn disputed_amount(env: &Env, job: &Job) -> Result<(Option<u32>, i128), MarketError> {
+    match read_milestones(env, job.id) {
+        Some(milestones) => {
+            let index = milestones
+                .iter()
+                .position(|m| m.status == MilestoneStatus::Disputed)
+                .ok_or(MarketError::MilestoneNotFound)? as u32;
+            Ok((Some(index), milestones.get_unchecked(index).amount))
+        }
+        None => Ok((None, job.amount)),
+    }
+}
+
+/// Pays out a decided dispute. A single-payment job is completed, while a
+/// milestone job resumes once the disputed milestone is resolved.
+fn settle_dispute(
+    env: &Env,
+    job: &mut Job,
+    milestone: Option<u32>,
+    amount: i128,
+    finder_share: i128,
+    artisan_share: i128,
+) -> Result<(), MarketError> {
+    let (platform_fee, juror_reward) =
+        pay_dispute_split(env, job, amount, finder_share, artisan_share)?;
+    report_dispute_outcome(env, job, finder_share, artisan_share);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Settlement(job.id));
+
+    match milestone {
+        Some(index) => {
+            let mut milestones =
+                read_milestones(env, job.id).ok_or(MarketError::NotMilestoneJob)?;
+            let mut resolved = milestones
+                .get(index)
+                .ok_or(MarketError::MilestoneNotFound)?;
+            resolved.status = MilestoneStatus::Resolved;
+            milestones.set(index, resolved);
+            write_milestones(env, job.id, &milestones);
+
+            clear_panel(env, job);
+            set_job_status(env, job, JobStatus::InProgress);
+            settle_if_all_milestones_done(env, job, &milestones);
+            write_job(env, job);
+
+            MilestoneDisputeResolved {
+                id: job.id,
+                index,
+                finder_share,
+                artisan_share,
+                platform_fee,
+                juror_reward,
+            }
+            .publish(env);
+        }
+        None => {
+            set_job_status(env, job, JobStatus::Completed);
+            write_job(env, job);
+
+            DisputeResolved {
+                id: job.id,
+                finder_share,
+                artisan_share,
+                platform_fee,
+                juror_reward,
+            }
+            .publish(env);
+        }
+    }
+    Ok(())
+}
+
+/// Settles an appealed ruling with the appeal's split. The bond is refunded
+/// if the appellant's share improved and otherwise goes to the fee
+/// recipients.
+fn settle_appeal(
+    env: &Env,
+    job: &mut Job,
+    ruling: Ruling,
+    finder_share: i128,
+    artisan_share: i128,
+) -> Result<(), MarketError> {
+    let appellant = ruling.appellant.clone().ok_or(MarketError::NoRuling)?;
+    env.storage().persistent().remove(&DataKey::Ruling(job.id));
+
+    settle_dispute(
+        env,
+        job,
+        ruling.milestone,
+        ruling.amount,
+        finder_share,
+        artisan_share,
+    )?;
+
+    let improved = if appellant == job.finder {
+        finder_share > ruling.finder_share
+    } else {
+        artisan_share > ruling.artisan_share
+    };
+    if ruling.bond > 0 {
+        if improved {
+            let token_client = token::TokenClient::new(env, &job.token);
+            token_client.transfer(&env.current_contract_address(), &appellant, &ruling.bond);
+        } else {
+            pay_platform_fee(env, &job.token, ruling.bond)?;
+        }
+    }
+
+    AppealDecided {
+        id: job.id,
+        appellant,
+        bond_refunded: improved,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Applies a panel's decided split. An appeal panel's split is final;
+/// otherwise the split is paid at once when appeals are disabled, or held as
+/// a ruling until the appeal window closes.
+fn apply_panel_decision(
+    env: &Env,
+    job: &mut Job,
+    milestone: Option<u32>,
+    amount: i128,
+    finder_share: i128,
+    artisan_share: i128,
+) -> Result<(), MarketError> {
+    if let Some(ruling) = read_ruling(env, job.id) {
+        return settle_appeal(env, job, ruling, finder_share, artisan_share);
+    }
+
+    let config = read_appeal_config(env);
+    if config.period_seconds == 0 {
+        return settle_dispute(env, job, milestone, amount, finder_share, artisan_share);
+    }
+
+    let ruled_at = env.ledger().timestamp();
+    let appeal_deadline = ruled_at
+        .checked_add(config.period_seconds)
+        .ok_or(MarketError::Overflow)?;
+    write_ruling(
+        env,
+        job.id,
+        &Ruling {
+            milestone,
+            amount,
+            finder_share,
+            artisan_share,
+            ruled_at,
+            panel_size: read_panel(env, job).len(),
+            appellant: None,
+            bond: 0,
+            appealed_at: 0,
+        },
+    );
+
+    RulingIssued {
+        id: job.id,
+        finder_share,
+        artisan_share,
+        appeal_deadline,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Appends an entry to a job's evidence log.
+fn append_evidence(
+    env: &Env,
+    job_id: u64,
+    party: Address,
+    evidence_hash: BytesN<32>,
+) -> Result<(), MarketError> {
+    let key = DataKey::Evidence(job_id);
+    let mut log: Vec<Evidence> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    if log.len() >= MAX_EVIDENCE {
+        return Err(MarketError::LimitReached);
+    }
+    log.push_back(Evidence {
+        submitted_by: party.clone(),
+        evidence_hash: evidence_hash.clone(),
+        submitted_at: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &log);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+
+    EvidenceSubmitted {
+        id: job_id,
+        submitted_by: party,
+        evidence_hash,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+fn deadline_passed(env: &Env, job: &Job) -> bool {
+    job.deadline != 0 && env.ledger().timestamp() > job.deadline
+}
+
+/// Checks that an `Assigned` or `InProgress` job has passed its deadline so
+/// the finder may take a missed-deadline remedy.
+fn require_missed_deadline(env: &Env, job: &Job, finder: &Address) -> Result<Address, MarketError> {
+    if job.finder != *finder {
+        return Err(MarketError::NotJobOwner);
+    }
+    if job.status != JobStatus::Assigned && job.status != JobStatus::InProgress {
+        return Err(MarketError::JobNotDisputable);
+    }
+    if !deadline_passed(env, job) {
+        return Err(MarketError::DeadlineNotPassed);
+    }
+    job.artisan.clone().ok_or(MarketError::JobNotAssigned)
+}
+
+fn assignment_timed_out(env: &Env, job_id: u64) -> Result<bool, MarketError> {
+    let assigned_at: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AssignmentTime(job_id))
+        .ok_or(MarketError::JobNotAssigned)?;
+    let timeout_at = assigned_at
+        .checked_add(ASSIGNMENT_TIMEOUT_SECONDS)
+        .ok_or(MarketError::Overflow)?;
+    Ok(env.ledger().timestamp() >= timeout_at)
+}
+
+#[contractimpl]
+impl MarketContract {
+    pub fn initialize(
+        env: Env,
+        registry_contract: Address,
+        admin: &Address,
+    ) -> Result<(), MarketError> {
+        if env.storage().instance().has(&DataKey::RegistryContract)
+            || env.storage().instance().has(&DataKey::Admin)
+        {
+            return Err(MarketError::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RegistryContract, &registry_contract);
+        env.storage().instance().set(&DataKey::Admin, admin);
+        env.storage().instance().set(&DataKey::IsPaused, &false);
+        env.storage().instance().set(
+            &DataKey::FeeRecipients,
+            &Vec::from_array(
+                &env,
+                [FeeRecipient {
+                    recipient: admin.clone(),
+                    weight_bps: 10000,
+                }],
+            ),
+        );
+        Ok(())
+    }
+
+    /// Escrows `amount` and lists a new job. `deadline` is the ledger
+    /// timestamp by which the work must be delivered, or 0 for no deadline. A
+    /// nonzero `expires_at` lets anyone refund the finder through `expire_job`
+    /// if the job is still open after that time.
+    pub fn create_job(
+        env: Env,
+        finder: Address,
+        token: Address,
+        amount: i128,
+        deadline: u64,
+        expires_at: u64,
+    ) -> Result<u64, MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        if expires_at != 0 && expires_at <= env.ledger().timestamp() {
+            return Err(MarketError::InvalidInput);
+        }
+
+        let id = escrow_new_job(&env, finder, token, amount, deadline)?;
+        if expires_at != 0 {
+            let key = DataKey::ListingExpiry(id);
+            env.storage().persistent().set(&key, &expires_at);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, 100_000, 500_000);
+        }
+
+        Ok(id)
+    }
+
+    /// Refunds the finder of an open job whose listing has expired and marks
+    /// it `Expired`, discarding its applications. Callable by anyone.
+    pub fn expire_job(env: Env, job_id: u64) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.status != JobStatus::Open {
+            return Err(MarketError::JobNotOpen);
+        }
+        if !listing_expired(&env, job_id) {
+            return Err(MarketError::DeadlineNotPassed);
+        }
+
+        let token_client = token::TokenClient::new(&env, &job.token);
+        token_client.transfer(&env.current_contract_address(), &job.finder, &job.amount);
+
+        set_job_status(&env, &mut job, JobStatus::Expired);
+        write_job(&env, &job);
+
+        let applicants_key = DataKey::JobApplicants(job_id);
+        let applicants: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&applicants_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        for artisan in applicants.iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Application(job_id, artisan));
+        }
+        env.storage().persistent().remove(&applicants_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ListingExpiry(job_id));
+
+        JobExpired {
+            id: job_id,
+            refunded: job.amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns when the job's listing expires, or 0 if it never does.
+    pub fn get_listing_expiry(env: Env, job_id: u64) -> u64 {
+        read_listing_expiry(&env, job_id)
+    }
+
+    /// Creates a job whose escrow is the sum of `milestones`, each of which
+    /// is submitted and released independently once the job is in progress.
+    pub fn create_milestone_job(
+        env: Env,
+        finder: Address,
+        token: Address,
+        milestones: Vec<MilestoneSpec>,
+    ) -> Result<u64, MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        if milestones.is_empty() || milestones.len() > MAX_MILESTONES {
+            return Err(MarketError::InvalidInput);
+        }
+
+        let mut total: i128 = 0;
+        let mut records = Vec::new(&env);
+        for spec in milestones.iter() {
+            if spec.amount <= 0 {
+                return Err(MarketError::InvalidInput);
+            }
+            total = total
+                .checked_add(spec.amount)
+                .ok_or(MarketError::Overflow)?;
+            records.push_back(Milestone {
+                description_hash: spec.description_hash,
+                amount: spec.amount,
+                due_date: spec.due_date,
+                status: MilestoneStatus::Pending,
+                submitted_at: 0,
+            });
+        }
+
+        let id = escrow_new_job(&env, finder, token, total, 0)?;
+        write_milestones(&env, id, &records);
+
+        Ok(id)
+    }
+
+    /// Escrows every cycle of a recurring engagement with `artisan` upfront.
+    /// The job is created already assigned; once started, each cycle is a
+    /// milestone the artisan submits from the start of its period and the
+    /// finder releases.
+    pub fn create_recurring_job(
+        env: Env,
+        finder: Address,
+        artisan: Address,
+        token: Address,
+        terms: RecurringTerms,
+        description_hash: BytesN<32>,
+    ) -> Result<u64, MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        if terms.cycles == 0
+            || terms.cycles > MAX_MILESTONES
+            || terms.amount_per_cycle <= 0
+            || terms.period_seconds == 0
+        {
+            return Err(MarketError::InvalidInput);
+        }
+        require_artisan_profile(&env, &artisan)?;
+
+        let total = terms
+            .amount_per_cycle
+            .checked_mul(terms.cycles as i128)
+            .ok_or(MarketError::Overflow)?;
+        let now = env.ledger().timestamp();
+        let mut records = Vec::new(&env);
+        for cycle in 1..=terms.cycles {
+            let due_date = terms
+                .period_seconds
+                .checked_mul(cycle as u64)
+                .and_then(|offset| now.checked_add(offset))
+                .ok_or(MarketError::Overflow)?;
+            records.push_back(Milestone {
+                description_hash: description_hash.clone(),
+                amount: terms.amount_per_cycle,
+                due_date,
+                status: MilestoneStatus::Pending,
+                submitted_at: 0,
+            });
+        }
+
+        let id = escrow_new_job(&env, finder, token, total, 0)?;
+        write_milestones(&env, id, &records);
+
+        let key = DataKey::Recurrence(id);
+        env.storage().persistent().set(&key, &terms);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+
+        let mut job = read_job(&env, id)?;
+        assign_job(&env, &mut job, artisan.clone());
+
+        RecurringJobCreated {
+            id,
+            artisan,
+            cycles: terms.cycles,
+            period_seconds: terms.period_seconds,
+        }
+        .publish(&env);
+
+        Ok(id)
+    }
+
+    /// Ends a recurring engagement early. Either party may terminate. Only
+    /// the cycle currently under way is kept, to be completed, released or
+    /// disputed as usual; cycles not yet begun, overdue cycles never
+    /// submitted, and every cycle if the job never started are refunded to
+    /// the finder.
+    pub fn terminate_recurring_job(
+        env: Env,
+        caller: Address,
+        job_id: u64,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        caller.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+        let terms = read_recurrence(&env, job_id).ok_or(MarketError::InvalidInput)?;
+        let mut milestones = read_milestones(&env, job_id).ok_or(MarketError::NotMilestoneJob)?;
+
+        if job.finder != caller && job.artisan.as_ref() != Some(&caller) {
+            return Err(MarketError::NotJobParty);
+        }
+        let started = match job.status {
+            JobStatus::Open | JobStatus::Assigned => false,
+            JobStatus::InProgress => true,
+            _ => return Err(MarketError::JobNotInProgress),
+        };
+
+        let now = env.ledger().timestamp();
+        let mut refunded: i128 = 0;
+        for index in 0..milestones.len() {
+            let mut milestone = milestones.get_unchecked(index);
+            let current = cycle_start(&terms, &milestone) <= now && now < milestone.due_date;
+            if milestone.status == MilestoneStatus::Pending && (!started || !current) {
+                refunded += milestone.amount;
+                milestone.status = MilestoneStatus::Cancelled;
+                milestones.set(index, milestone);
+            }
+        }
+        write_milestones(&env, job_id, &milestones);
+
+        if refunded > 0 {
+            let token_client = token::TokenClient::new(&env, &job.token);
+            token_client.transfer(&env.current_contract_address(), &job.finder, &refunded);
+        }
+
+        // A job with every cycle refunded was never delivered on, so it is
+        // cancelled rather than completed.
+        let all_refunded = milestones
+            .iter()
+            .all(|milestone| milestone.status == MilestoneStatus::Cancelled);
+        if started && !all_refunded {
+            settle_if_all_milestones_done(&env, &mut job, &milestones);
+        } else {
+            set_job_status(&env, &mut job, JobStatus::Cancelled);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::AssignmentTime(job_id));
+        }
+        write_job(&env, &job);
+
+        RecurringJobTerminated {
+            id: job_id,
+            terminated_by: caller,
+            refunded,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_recurring_terms(env: Env, job_id: u64) -> Option<RecurringTerms> {
+        read_recurrence(&env, job_id)
+    }
+
+    pub fn assign_artisan(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        artisan: Address,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+
+        let mut job = read_job(&env, job_id)?;
+
+        finder.require_auth();
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+
+        if job.status != JobStatus::Open {
+            return Err(MarketError::JobNotOpen);
+        }
+
+        require_artisan_profile(&env, &artisan)?;
+
+        assign_job(&env, &mut job, artisan);
+
+        Ok(())
+    }
+
+    /// Assigns an `Open` job to an applicant at their quoted price. The
+    /// escrow is topped up from, or refunded to, the finder so that it
+    /// matches the quote, and a job without a deadline gets one from the
+    /// bid's estimated duration.
+    pub fn accept_bid(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        artisan: Address,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+        if job.status != JobStatus::Open {
+            return Err(MarketError::JobNotOpen);
+        }
+        require_single_payment_job(&env, job_id)?;
+
+        let bid: JobApplicationRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Application(job_id, artisan.clone()))
+            .ok_or(MarketError::ApplicationNotFound)?;
+
+        require_artisan_profile(&env, &artisan)?;
+        require_allowed_amount(&env, &job.token, bid.quote)?;
+
+        if job.deadline == 0 && bid.duration > 0 {
+            job.deadline = env
+                .ledger()
+                .timestamp()
+                .checked_add(bid.duration)
+                .ok_or(MarketError::Overflow)?;
+        }
+
+        let token_client = token::TokenClient::new(&env, &job.token);
+        let contract = env.current_contract_address();
+        if bid.quote > job.amount {
+            token_client.transfer(&finder, &contract, &(bid.quote - job.amount));
+        } else if bid.quote < job.amount {
+            token_client.transfer(&contract, &finder, &(job.amount - bid.quote));
+        }
+        job.amount = bid.quote;
+
+        assign_job(&env, &mut job, artisan.clone());
+
+        BidAccepted {
+            id: job_id,
+            artisan,
+            amount: bid.quote,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn reopen_timed_out_assignment(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+        if job.status != JobStatus::Assigned {
+            return Err(MarketError::JobNotAssigned);
+        }
+        require_non_recurring_job(&env, job_id)?;
+
+        if !assignment_timed_out(&env, job_id)? {
+            return Err(MarketError::AssignmentNotTimedOut);
+        }
+
+        let artisan = job.artisan.take().ok_or(MarketError::JobNotAssigned)?;
+        set_job_status(&env, &mut job, JobStatus::Open);
+
+        write_job(&env, &job);
+        index_remove(&env, &JobIndex::Artisan(artisan.clone()), job_id);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AssignmentTime(job_id));
+
+        AssignmentTimedOut {
+            id: job_id,
+            artisan,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Lets the assigned artisan turn down an assignment they have not
+    /// started, reopening the job for other applicants.
+    pub fn decline_assignment(env: Env, artisan: Address, job_id: u64) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.artisan != Some(artisan.clone()) {
+            return Err(MarketError::NotAssignedArtisan);
+        }
+        if job.status != JobStatus::Assigned {
+            return Err(MarketError::JobNotAssigned);
+        }
+        require_non_recurring_job(&env, job_id)?;
+
+        job.artisan = None;
+        set_job_status(&env, &mut job, JobStatus::Open);
+
+        write_job(&env, &job);
+        index_remove(&env, &JobIndex::Artisan(artisan.clone()), job_id);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AssignmentTime(job_id));
+
+        AssignmentDeclined {
+            id: job_id,
+            artisan,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Lets the artisan walk away from a started job. The finder is refunded
+    /// in full and the abandonment is recorded against the artisan's registry
+    /// reputation when this market reports to the registry.
+    ///
+    /// Single-payment jobs only; milestone jobs return `MilestoneJob`, since
+    /// their escrow is settled milestone by milestone and recurring ones end
+    /// through `terminate_recurring_job`.
+    pub fn abandon_job(env: Env, artisan: Address, job_id: u64) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.artisan != Some(artisan.clone()) {
+            return Err(MarketError::NotAssignedArtisan);
+        }
+        if job.status != JobStatus::InProgress {
+            return Err(MarketError::JobNotInProgress);
+        }
+        require_single_payment_job(&env, job_id)?;
+
+        let token_client = token::TokenClient::new(&env, &job.token);
+        token_client.transfer(&env.current_contract_address(), &job.finder, &job.amount);
+
+        set_job_status(&env, &mut job, JobStatus::Cancelled);
+        write_job(&env, &job);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AssignmentTime(job_id));
+        report_reputation(&env, &artisan, registry::ReputationUpdate::JobAbandoned);
+
+        JobAbandoned {
+            id: job_id,
+            artisan,
+        }
+        .publish(&env);
+        JobCancelled { id: job_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Reassigns a stalled assignment without moving or refunding escrow.
+    ///
+    /// A finder may reassign only while the job is still `Assigned` and the
+    /// current artisan has not started it within the assignment timeout.
+    pub fn reassign_artisan(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        new_artisan: Address,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+        if job.status != JobStatus::Assigned {
+            return Err(MarketError::JobNotAssigned);
+        }
+        require_non_recurring_job(&env, job_id)?;
+
+        let previous_artisan = job.artisan.clone().ok_or(MarketError::JobNotAssigned)?;
+        if previous_artisan == new_artisan {
+            return Err(MarketError::ArtisanAlreadyAssigned);
+        }
+
+        if !assignment_timed_out(&env, job_id)? {
+            return Err(MarketError::AssignmentNotTimedOut);
+        }
+
+        require_artisan_profile(&env, &new_artisan)?;
+
+        job.artisan = Some(new_artisan.clone());
+        write_job(&env, &job);
+        index_remove(&env, &JobIndex::Artisan(previous_artisan.clone()), job_id);
+        index_insert(&env, &JobIndex::Artisan(new_artisan.clone()), job_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AssignmentTime(job_id), &env.ledger().timestamp());
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::AssignmentTime(job_id), 100_000, 500_000);
+
+        JobReassigned {
+            id: job_id,
+            previous_artisan,
+            new_artisan,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Records a bid on an `Open` job: the artisan's `quote` for the work and
+    /// the estimated `duration` in seconds to deliver it.
+    pub fn apply_for_job(
+        env: Env,
+        artisan: Address,
+        job_id: u64,
+        quote: i128,
+        duration: u64,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
+
+        let job = read_job(&env, job_id)?;
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+
+        if job.status != JobStatus::Open || listing_expired(&env, job_id) {
+            return Err(MarketError::JobNotOpen);
+        }
+
+        require_artisan_profile(&env, &artisan)?;
+
+        if quote <= 0 {
+            return Err(MarketError::InvalidInput);
+        }
+
+        let app_key = DataKey::Application(job_id, artisan.clone());
+        if env.storage().persistent().has(&app_key) {
+            return Err(MarketError::DuplicateApplication);
+        }
+
+        let record = JobApplicationRecord {
+            job_id,
+            artisan: artisan.clone(),
+            applied_at: env.ledger().timestamp(),
+            quote,
+            duration,
+        };
+
+        env.storage().persistent().set(&app_key, &record);
+        env.storage()
+            .persistent()
+            .extend_ttl(&app_key, 100_000, 500_000);
+
+        let applicants_key = DataKey::JobApplicants(job_id);
+        let mut applicants: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&applicants_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        applicants.push_back(artisan.clone());
+        env.storage().persistent().set(&applicants_key, &applicants);
+        env.storage()
+            .persistent()
+            .extend_ttl(&applicants_key, 100_000, 500_000);
+
+        JobApplication {
+            id: job_id,
+            artisan,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Lets an artisan pull their application from a job that is still `Open`.
+    pub fn withdraw_application(
+        env: Env,
+        artisan: Address,
+        job_id: u64,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
+
+        let job = read_job(&env, job_id)?;
+        if job.status != JobStatus::Open {
+            return Err(MarketError::JobNotOpen);
+        }
+
+        remove_application(&env, job_id, &artisan)?;
+
+        ApplicationWithdrawn {
+            id: job_id,
+            artisan,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Lets a finder turn down an application on their `Open` job.
+    pub fn reject_application(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        artisan: Address,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let job = read_job(&env, job_id)?;
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+        if job.status != JobStatus::Open {
+            return Err(MarketError::JobNotOpen);
+        }
+
+        remove_application(&env, job_id, &artisan)?;
+
+        ApplicationRejected {
+            id: job_id,
+            artisan,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn start_job(env: Env, artisan: Address, job_id: u64) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.status != JobStatus::Assigned {
+            return Err(MarketError::JobNotAssigned);
+        }
+
+        if job.artisan != Some(artisan.clone()) {
+            return Err(MarketError::NotAssignedArtisan);
+        }
+
+        if deadline_passed(&env, &job) {
+            return Err(MarketError::DeadlinePassed);
+        }
+
+        set_job_status(&env, &mut job, JobStatus::InProgress);
+        job.start_time = env.ledger().timestamp();
+
+        // Pay any agreed advance out of escrow; later payouts only see the rest.
+        let advance_key = DataKey::Advance(job_id);
+        let mut advance: Advance = env
+            .storage()
+            .persistent()
+            .get(&advance_key)
+            .unwrap_or_default();
+        let advance_amount = (job.amount * (advance.advance_bps as i128)) / 10000;
+        if advance_amount > 0 {
+            let payout = release_to_artisan(&env, &job, &artisan, advance_amount)?;
+            job.amount -= advance_amount;
+            advance.paid = advance_amount;
+            env.storage().persistent().set(&advance_key, &advance);
+            env.storage()
+                .persistent()
+                .extend_ttl(&advance_key, 100_000, 500_000);
+
+            AdvanceReleased {
+                id: job_id,
+                artisan: artisan.clone(),
+                amount: payout,
+            }
+            .publish(&env);
+        }
+
+        write_job(&env, &job);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AssignmentTime(job_id));
+
+        JobStarted {
+            id: job_id,
+            artisan,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Offers an upfront advance of `advance_bps` of the escrow, paid to the
+    /// artisan when they start the job, up to `MAX_ADVANCE_BPS`. Part of the
+    /// listing's terms, so it can only be changed while the job is open;
+    /// zero removes it.
+    pub fn set_advance(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        advance_bps: u32,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+        if job.status != JobStatus::Open {
+            return Err(MarketError::JobNotOpen);
+        }
+        require_single_payment_job(&env, job_id)?;
+
+        if advance_bps > MAX_ADVANCE_BPS {
+            return Err(MarketError::InvalidInput);
+        }
+
+        let key = DataKey::Advance(job_id);
+        env.storage().persistent().set(
+            &key,
+            &Advance {
+                advance_bps,
+                paid: 0,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+
+        AdvanceSet {
+            id: job_id,
+            advance_bps,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_advance(env: Env, job_id: u64) -> Advance {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Advance(job_id))
+            .unwrap_or_default()
+    }
+
+    pub fn cancel_job(env: Env, finder: Address, job_id: u64) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+
+        if job.status != JobStatus::Open {
+            return Err(MarketError::JobNotOpen);
+        }
+
+        let token_client = token::TokenClient::new(&env, &job.token);
+        token_client.transfer(&env.current_contract_address(), &finder, &job.amount);
+
+        set_job_status(&env, &mut job, JobStatus::Cancelled);
+
+        write_job(&env, &job);
+
+        JobCancelled { id: job_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Proposes cancelling an `Assigned` or `InProgress` job, splitting its
+    /// escrow between a refund to the finder and compensation for the
+    /// artisan. The split must cover the whole escrow; compensation is paid
+    /// net of the platform fee. Replaces any earlier proposal.
+    pub fn propose_cancellation(
+        env: Env,
+        proposer: Address,
+        job_id: u64,
+        finder_refund: i128,
+        artisan_compensation: i128,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        proposer.require_auth();
+
+        let job = read_job(&env, job_id)?;
+        let artisan = job.artisan.clone().ok_or(MarketError::JobNotAssigned)?;
+        if job.finder != proposer && artisan != proposer {
+            return Err(MarketError::NotJobParty);
+        }
+        if job.status != JobStatus::Assigned && job.status != JobStatus::InProgress {
+            return Err(MarketError::JobNotAssigned);
+        }
+        require_single_payment_job(&env, job_id)?;
+
+        if finder_refund < 0
+            || artisan_compensation < 0
+            || finder_refund.checked_add(artisan_compensation) != Some(job.amount)
+        {
+            return Err(MarketError::InvalidShares);
+        }
+
+        let key = DataKey::Cancellation(job_id);
+        env.storage().persistent().set(
+            &key,
+            &CancellationOffer {
+                proposer: proposer.clone(),
+                artisan,
+                finder_refund,
+                artisan_compensation,
+                proposed_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+
+        CancellationProposed {
+            id: job_id,
+            proposer,
+            finder_refund,
+            artisan_compensation,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Accepts the other party's cancellation proposal, refunding and
+    /// compensating from escrow and cancelling the job.
+    pub fn accept_cancellation(env: Env, party: Address, job_id: u64) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        party.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+        let artisan = job.artisan.clone().ok_or(MarketError::JobNotAssigned)?;
+        if job.finder != party && artisan != party {
+            return Err(MarketError::NotJobParty);
+        }
+        if job.status != JobStatus::Assigned && job.status != JobStatus::InProgress {
+            return Err(MarketError::JobNotAssigned);
+        }
+
+        let key = DataKey::Cancellation(job_id);
+        let offer: CancellationOffer = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(MarketError::OfferNotFound)?;
+        // A proposal made with a previously assigned artisan is void.
+        if offer.artisan != artisan {
+            return Err(MarketError::OfferNotFound);
+        }
+        if offer.proposer == party {
+            return Err(MarketError::Unauthorized);
+        }
+        if offer.finder_refund.checked_add(offer.artisan_compensation) != Some(job.amount) {
+            return Err(MarketError::InvalidShares);
+        }
+        env.storage().persistent().remove(&key);
+
+        if offer.finder_refund > 0 {
+            let token_client = token::TokenClient::new(&env, &job.token);
+            token_client.transfer(
+                &env.current_contract_address(),
+                &job.finder,
+                &offer.finder_refund,
+            );
+        }
+        if offer.artisan_compensation > 0 {
+            release_to_artisan(&env, &job, &artisan, offer.artisan_compensation)?;
+        }
+
+        set_job_status(&env, &mut job, JobStatus::Cancelled);
+        write_job(&env, &job);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AssignmentTime(job_id));
+
+        JobMutuallyCancelled {
+            id: job_id,
+            finder_refund: offer.finder_refund,
+            artisan_compensation: offer.artisan_compensation,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_cancellation(env: Env, job_id: u64) -> Option<CancellationOffer> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Cancellation(job_id))
+    }
+
+    pub fn complete_job(env: Env, artisan: Address, job_id: u64) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.artisan != Some(artisan.clone()) {
+            return Err(MarketError::NotAssignedArtisan);
+        }
+
+        if job.status != JobStatus::InProgress {
+            return Err(MarketError::JobNotInProgress);
+        }
+        require_single_payment_job(&env, job_id)?;
+
+        if deadline_passed(&env, &job) {
+            return Err(MarketError::DeadlinePassed);
+        }
+
+        set_job_status(&env, &mut job, JobStatus::PendingReview);
+        job.end_time = env.ledger().timestamp();
+
+        write_job(&env, &job);
+
+        JobCompleted {
+            id: job_id,
+            artisan,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Sends a job under review back to the artisan for changes, up to
+    /// `MAX_REVISIONS` rounds. The auto-release clock restarts when the
+    /// artisan next calls `complete_job`, and the notes are added to the
+    /// job's evidence log.
+    pub fn request_revision(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        notes_hash: BytesN<32>,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+        if job.status != JobStatus::PendingReview {
+            return Err(MarketError::JobNotPendingReview);
+        }
+        // The artisan could not resubmit past the deadline.
+        if deadline_passed(&env, &job) {
+            return Err(MarketError::DeadlinePassed);
+        }
+
+        let key = DataKey::Revisions(job_id);
+        let mut revisions: Vec<Revision> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if revisions.len() >= MAX_REVISIONS {
+            return Err(MarketError::LimitReached);
+        }
+        let round = revisions.len() + 1;
+
+        append_evidence(&env, job_id, finder, notes_hash.clone())?;
+        revisions.push_back(Revision {
+            round,
+            notes_hash: notes_hash.clone(),
+            requested_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &revisions);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+
+        set_job_status(&env, &mut job, JobStatus::InProgress);
+        job.end_time = 0;
+        write_job(&env, &job);
+
+        RevisionRequested {
+            id: job_id,
+            round,
+            notes_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the job's revision rounds in order.
+    pub fn get_revisions(env: Env, job_id: u64) -> Vec<Revision> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Revisions(job_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn confirm_delivery(env: Env, finder: Address, job_id: u64) -> Result<(), MarketError> {
+        Self::confirm_delivery_with_tip(env, finder, job_id, 0)
+    }
+
+    /// Confirms delivery and pays the artisan an extra `tip` in the job token
+    /// straight from the finder. Tips are charged the tip fee rather than the
+    /// platform fee.
+    pub fn confirm_delivery_with_tip(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        tip: i128,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        if tip < 0 {
+            return Err(MarketError::InvalidInput);
+        }
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+
+        if job.status != JobStatus::PendingReview {
+            return Err(MarketError::JobNotPendingReview);
+        }
+
+        let artisan = job.artisan.clone().ok_or(MarketError::JobNotAssigned)?;
+        let payout = release_to_artisan(&env, &job, &artisan, job.amount)?;
+        let tip = if tip > 0 {
+            pay_tip(&env, &job, &artisan, tip)?
+        } else {
+            0
+        };
+
+        set_job_status(&env, &mut job, JobStatus::Completed);
+        write_job(&env, &job);
+        report_completion(&env, &job);
+
+        FundsReleased {
+            id: job_id,
+            artisan,
+            amount: payout,
+            tip,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn raise_dispute(
+        env: Env,
+        caller: Address,
+        job_id: u64,
+        reason: String,
+    ) -> Result<(), MarketError> {
+        caller.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != caller && job.artisan.as_ref() != Some(&caller) {
+            return Err(MarketError::NotJobParty);
+        }
+
+        if job.status != JobStatus::InProgress && job.status != JobStatus::PendingReview {
+            return Err(MarketError::JobNotDisputable);
+        }
+        require_single_payment_job(&env, job_id)?;
+
+        job.dispute_reason = Some(reason);
+        open_dispute(&env, &mut job);
+        write_job(&env, &job);
+
+        DisputeRaised {
+            id: job_id,
+            raised_by: caller,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn auto_release_funds(env: Env, artisan: Address, job_id: u64) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.status != JobStatus::PendingReview {
+            return Err(MarketError::JobNotPendingReview);
+        }
+
+        let artisan_from_job = job.artisan.as_ref().ok_or(MarketError::JobNotAssigned)?;
+        if artisan_from_job != &artisan {
+            return Err(MarketError::NotAssignedArtisan);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let release_time = job.end_time + AUTO_RELEASE_SECONDS;
+
+        if current_time <= release_time {
+            return Err(MarketError::ReviewPeriodActive);
+        }
+
+        let payout = release_to_artisan(&env, &job, &artisan, job.amount)?;
+
+        set_job_status(&env, &mut job, JobStatus::Completed);
+        write_job(&env, &job);
+        report_completion(&env, &job);
+
+        FundsReleased {
+            id: job_id,
+            artisan,
+            amount: payout,
+            tip: 0,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Moves the job's deadline `extra_time` seconds past the later of its
+    /// current deadline and now, so a job without a deadline, or one already
+    /// missed, gets a deadline in the future.
+    pub fn extend_deadline(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        extra_time: u64,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+
+        if job.status == JobStatus::Completed
+            || job.status == JobStatus::Cancelled
+            || job.status == JobStatus::Expired
+        {
+            return Err(MarketError::JobFinalized);
+        }
+
+        job.deadline = job
+            .deadline
+            .max(env.ledger().timestamp())
+            .checked_add(extra_time)
+            .ok_or(MarketError::Overflow)?;
+
+        write_job(&env, &job);
+
+        DeadlineExtended {
+            id: job_id,
+            extra_time,
+            new_deadline: job.deadline,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Refunds the full escrow to the finder once an `Assigned` or
+    /// `InProgress` job has passed its deadline, cancelling the job.
+    pub fn reclaim_missed_deadline(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+        let artisan = require_missed_deadline(&env, &job, &finder)?;
+        require_single_payment_job(&env, job_id)?;
+
+        let token_client = token::TokenClient::new(&env, &job.token);
+        token_client.transfer(&env.current_contract_address(), &finder, &job.amount);
+
+        set_job_status(&env, &mut job, JobStatus::Cancelled);
+        write_job(&env, &job);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AssignmentTime(job_id));
+
+        DeadlineMissed {
+            id: job_id,
+            artisan,
+            deadline: job.deadline,
+        }
+        .publish(&env);
+        JobCancelled { id: job_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Opens a dispute on an `Assigned` or `InProgress` job that has passed
+    /// its deadline, leaving the split of escrow to a juror.
+    pub fn dispute_missed_deadline(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+    ) -> Result<(), MarketError> {
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+        let artisan = require_missed_deadline(&env, &job, &finder)?;
+        require_single_payment_job(&env, job_id)?;
+
+        open_dispute(&env, &mut job);
+        write_job(&env, &job);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AssignmentTime(job_id));
+
+        DeadlineMissed {
+            id: job_id,
+            artisan,
+            deadline: job.deadline,
+        }
+        .publish(&env);
+        DisputeRaised {
+            id: job_id,
+            raised_by: finder,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Adds `added_amount` to a job's escrow. Not allowed while the job is
+    /// disputed, since votes, rulings and offers already cover the escrow as
+    /// it was.
+    pub fn increase_budget(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        added_amount: i128,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+
+        if job.status == JobStatus::Completed
+            || job.status == JobStatus::Cancelled
+            || job.status == JobStatus::Expired
+        {
+            return Err(MarketError::JobFinalized);
+        }
+        if job.status == JobStatus::Disputed {
+            return Err(MarketError::JobDisputed);
+        }
+        require_single_payment_job(&env, job_id)?;
+
+        let new_amount = job
+            .amount
+            .checked_add(added_amount)
+            .ok_or(MarketError::Overflow)?;
+        require_allowed_amount(&env, &job.token, new_amount)?;
+
+        let token_client = token::TokenClient::new(&env, &job.token);
+        token_client.transfer(&finder, env.current_contract_address(), &added_amount);
+
+        job.amount = new_amount;
+
+        write_job(&env, &job);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Cancellation(job_id));
+
+        BudgetIncreased {
+            id: job_id,
+            added_amount,
+            new_amount: job.amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn transfer_admin(
+        env: Env,
+        old_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        require_admin(&env, &old_admin)?;
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+
+        AdminTransferred { new_admin }.publish(&env);
+
+        Ok(())
+    }
+
+    pub fn toggle_contract_pause(env: Env, admin: Address) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
 
-        let registry_client = registry::Client::new(&env, &registry_contract);
-        let profile = registry_client.get_profile(&artisan);
+        let paused = !is_paused(&env)?;
+        env.storage().instance().set(&DataKey::IsPaused, &paused);
+
+        PauseStateChanged { paused }.publish(&env);
+
+        Ok(())
+    }
 
-        if profile.role != 3 {
-            panic!("User is not a verified Artisan");
-        }
-        if profile.is_blacklisted {
-            panic!("User is blacklisted");
+    pub fn emergency_withdraw(
+        env: Env,
+        admin: Address,
+        token: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        if !is_paused(&env)? {
+            return Err(MarketError::ContractNotPaused);
         }
 
-        job.artisan = Some(artisan.clone());
-        job.status = JobStatus::Assigned;
+        let token_client = token::TokenClient::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &to, &amount);
 
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .set(&DataKey::AssignmentTime(job_id), &env.ledger().timestamp());
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::AssignmentTime(job_id), 100_000, 500_000);
+        EmergencyWithdraw { token, amount, to }.publish(&env);
 
-        JobAssigned {
-            id: job_id,
-            artisan,
+        Ok(())
+    }
+
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        ContractUpgraded {
+            hash: new_wasm_hash,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn reopen_timed_out_assignment(env: Env, finder: Address, job_id: u64) {
-        assert!(!is_paused(&env), "Contract Paused");
-        finder.require_auth();
+    /// Rewrites up to `limit` jobs stored in the pre-`fee_bps` layout,
+    /// starting at `start_id`, snapshotting the current platform fee onto
+    /// each. Jobs already in the current layout are left untouched. Returns
+    /// the number of jobs migrated.
+    pub fn migrate_jobs(
+        env: Env,
+        admin: Address,
+        start_id: u64,
+        limit: u32,
+    ) -> Result<u32, MarketError> {
+        require_admin(&env, &admin)?;
 
-        let mut job: Job = env
+        let counter: u64 = env
             .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
-
-        if job.finder != finder {
-            panic!("Not job owner");
-        }
-        if job.status != JobStatus::Assigned {
-            panic!("Job is not assigned");
+            .instance()
+            .get(&DataKey::JobCounter)
+            .unwrap_or(0);
+        let fee_bps = read_fee_bps(&env);
+        let fee_field = Symbol::new(&env, "fee_bps");
+
+        let mut migrated = 0;
+        let end_id = start_id
+            .saturating_add(limit.min(MAX_PAGE_SIZE) as u64)
+            .min(counter.saturating_add(1));
+        for job_id in start_id.max(1)..end_id {
+            let key = DataKey::Job(job_id);
+            let raw: Option<Map<Symbol, Val>> = env.storage().persistent().get(&key);
+            match raw {
+                Some(fields) if !fields.contains_key(fee_field.clone()) => {}
+                _ => continue,
+            }
+
+            let legacy: LegacyJob = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .ok_or(MarketError::JobNotFound)?;
+            let job = Job {
+                id: legacy.id,
+                finder: legacy.finder,
+                artisan: legacy.artisan,
+                juror: legacy.juror,
+                token: legacy.token,
+                amount: legacy.amount,
+                status: legacy.status,
+                start_time: legacy.start_time,
+                end_time: legacy.end_time,
+                deadline: legacy.deadline,
+                dispute_reason: legacy.dispute_reason,
+                fee_bps,
+            };
+            write_job(&env, &job);
+            migrated += 1;
         }
 
-        let assigned_at: u64 = env
+        Ok(migrated)
+    }
+
+    /// Adds up to `limit` jobs, starting at `start_id`, to the status, finder
+    /// and artisan indexes. Used to list jobs created before the indexes
+    /// existed; run `migrate_jobs` first, as jobs still in the pre-`fee_bps`
+    /// layout are skipped. Returns the number of jobs newly indexed.
+    pub fn index_jobs(
+        env: Env,
+        admin: Address,
+        start_id: u64,
+        limit: u32,
+    ) -> Result<u32, MarketError> {
+        require_admin(&env, &admin)?;
+
+        let counter: u64 = env
             .storage()
-            .persistent()
-            .get(&DataKey::AssignmentTime(job_id))
-            .expect("Assignment time not found");
-        let timeout_at = assigned_at
-            .checked_add(ASSIGNMENT_TIMEOUT_SECONDS)
-            .expect("Assignment timeout overflow");
-        if env.ledger().timestamp() < timeout_at {
-            panic!("Assignment has not timed out");
+            .instance()
+            .get(&DataKey::JobCounter)
+            .unwrap_or(0);
+        let fee_field = Symbol::new(&env, "fee_bps");
+
+        let mut indexed = 0;
+        let end_id = start_id
+            .saturating_add(limit.min(MAX_PAGE_SIZE) as u64)
+            .min(counter.saturating_add(1));
+        for job_id in start_id.max(1)..end_id {
+            let raw: Option<Map<Symbol, Val>> =
+                env.storage().persistent().get(&DataKey::Job(job_id));
+            match raw {
+                Some(fields) if fields.contains_key(fee_field.clone()) => {}
+                _ => continue,
+            }
+
+            let job = read_job(&env, job_id)?;
+            let mut added = index_insert(&env, &JobIndex::Status(job.status.clone()), job_id);
+            added |= index_insert(&env, &JobIndex::Finder(job.finder.clone()), job_id);
+            if let Some(artisan) = job.artisan.clone() {
+                added |= index_insert(&env, &JobIndex::Artisan(artisan), job_id);
+            }
+            if added {
+                indexed += 1;
+            }
         }
 
-        let artisan = job.artisan.take().expect("Job has no assigned artisan");
-        job.status = JobStatus::Open;
+        Ok(indexed)
+    }
+
+    pub fn set_platform_fee(env: Env, admin: Address, fee_bps: u32) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        if fee_bps > 1000 {
+            return Err(MarketError::FeeTooHigh);
+        }
 
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .remove(&DataKey::AssignmentTime(job_id));
         env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+            .instance()
+            .set(&DataKey::PlatformFee, &fee_bps);
 
-        AssignmentTimedOut {
-            id: job_id,
-            artisan,
+        FeeUpdated {
+            new_fee_bps: fee_bps,
         }
         .publish(&env);
-    }
 
-    /// Reassigns a stalled assignment without moving or refunding escrow.
-    ///
-    /// A finder may reassign only while the job is still `Assigned` and the
-    /// current artisan has not started it within the assignment timeout.
-    pub fn reassign_artisan(env: Env, finder: Address, job_id: u64, new_artisan: Address) {
-        assert!(!is_paused(&env), "Contract Paused");
-        finder.require_auth();
+        Ok(())
+    }
 
-        let registry_contract: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::RegistryContract)
-            .expect("Contract not initialized");
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+    /// Sets the fee charged on tips, independent of the platform fee. Zero,
+    /// the default, passes tips through in full.
+    pub fn set_tip_fee(env: Env, admin: Address, fee_bps: u32) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
 
-        if job.finder != finder {
-            panic!("Not job owner");
-        }
-        if job.status != JobStatus::Assigned {
-            panic!("Job is not assigned");
+        if fee_bps > 1000 {
+            return Err(MarketError::FeeTooHigh);
         }
 
-        let previous_artisan = job.artisan.clone().expect("Job has no assigned artisan");
-        if previous_artisan == new_artisan {
-            panic!("Artisan is already assigned");
+        env.storage().instance().set(&DataKey::TipFee, &fee_bps);
+
+        TipFeeUpdated {
+            new_fee_bps: fee_bps,
         }
+        .publish(&env);
 
-        let assigned_at: u64 = env
-            .storage()
+        Ok(())
+    }
+
+    pub fn get_tip_fee(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::TipFee).unwrap_or(0)
+    }
+
+    /// Returns what `artisan` has been paid in `token` across all jobs.
+    pub fn get_earnings(env: Env, artisan: Address, token: Address) -> Earnings {
+        env.storage()
             .persistent()
-            .get(&DataKey::AssignmentTime(job_id))
-            .expect("Assignment time not found");
-        let timeout_at = assigned_at
-            .checked_add(ASSIGNMENT_TIMEOUT_SECONDS)
-            .expect("Assignment timeout overflow");
-        if env.ledger().timestamp() < timeout_at {
-            panic!("Assignment has not timed out");
-        }
+            .get(&DataKey::Earnings(artisan, token))
+            .unwrap_or_default()
+    }
 
-        let registry_client = registry::Client::new(&env, &registry_contract);
-        let profile = registry_client.get_profile(&new_artisan);
-        if profile.role != 3 {
-            panic!("User is not a verified Artisan");
+    /// Replaces the platform fee recipients. Weights must be positive and sum
+    /// to 10000 bps. Fee recipients are independent of the admin, so
+    /// `transfer_admin` never redirects revenue.
+    pub fn set_fee_recipients(
+        env: Env,
+        admin: Address,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        if recipients.is_empty() || recipients.len() > MAX_FEE_RECIPIENTS {
+            return Err(MarketError::InvalidInput);
         }
-        if profile.is_blacklisted {
-            panic!("User is blacklisted");
+        let mut total: u32 = 0;
+        for entry in recipients.iter() {
+            if entry.weight_bps == 0 {
+                return Err(MarketError::InvalidInput);
+            }
+            total = total.saturating_add(entry.weight_bps);
+        }
+        if total != 10000 {
+            return Err(MarketError::InvalidInput);
         }
 
-        job.artisan = Some(new_artisan.clone());
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .set(&DataKey::AssignmentTime(job_id), &env.ledger().timestamp());
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
         env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::AssignmentTime(job_id), 100_000, 500_000);
+            .instance()
+            .set(&DataKey::FeeRecipients, &recipients);
 
-        JobReassigned {
-            id: job_id,
-            previous_artisan,
-            new_artisan,
+        FeeRecipientsUpdated { recipients }.publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_fee_recipients(env: Env) -> Result<Vec<FeeRecipient>, MarketError> {
+        read_fee_recipients(&env)
+    }
+
+    /// Sets how long disputes may wait for a panel and for its ruling, and
+    /// the share of the net escrow, in basis points, the finder receives when
+    /// a dispute expires.
+    pub fn set_dispute_timeouts(
+        env: Env,
+        admin: Address,
+        assignment_seconds: u64,
+        ruling_seconds: u64,
+        default_finder_bps: u32,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        if default_finder_bps > 10000 {
+            return Err(MarketError::InvalidInput);
+        }
+
+        env.storage().instance().set(
+            &DataKey::DisputeTimeouts,
+            &DisputeTimeouts {
+                assignment_seconds,
+                ruling_seconds,
+                default_finder_bps,
+            },
+        );
+
+        DisputeTimeoutsUpdated {
+            assignment_seconds,
+            ruling_seconds,
+            default_finder_bps,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn apply_for_job(env: Env, artisan: Address, job_id: u64) {
-        assert!(!is_paused(&env), "Contract Paused");
-        artisan.require_auth();
+    pub fn get_dispute_timeouts(env: Env) -> DisputeTimeouts {
+        read_dispute_timeouts(&env)
+    }
 
-        let registry_contract: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::RegistryContract)
-            .expect("Contract not initialized");
+    /// Settles a dispute with the default split once no panel was seated
+    /// within the assignment timeout, or the seated panel failed to rule
+    /// within the ruling timeout. Callable by anyone. Before then the admin
+    /// can still replace the panel with `assign_juror` or `assign_jury`.
+    ///
+    /// An appealed ruling expires the same way, counting from the appeal
+    /// until a new panel is seated and from the seating after that. It then
+    /// stands as the final split and the appeal bond is refunded.
+    pub fn expire_dispute(env: Env, job_id: u64) -> Result<(), MarketError> {
+        let mut job = read_job(&env, job_id)?;
+
+        if job.status != JobStatus::Disputed {
+            return Err(MarketError::JobNotDisputed);
+        }
 
-        let job: Job = env
+        let timeouts = read_dispute_timeouts(&env);
+        let ruling = read_ruling(&env, job_id);
+        let assigned_at: Option<u64> = env
             .storage()
             .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
-
-        if job.status != JobStatus::Open {
-            panic!("Job is not open");
+            .get(&DataKey::JurorAssignedAt(job_id));
+        // Disputes raised before their clocks were recorded have no start
+        // time and never expire.
+        let (started_at, timeout) = match &ruling {
+            Some(ruling) if ruling.appellant.is_none() => {
+                return Err(MarketError::RulingPending);
+            }
+            Some(ruling) => match assigned_at {
+                Some(assigned_at) if assigned_at > ruling.appealed_at => {
+                    (Some(assigned_at), timeouts.ruling_seconds)
+                }
+                _ => (Some(ruling.appealed_at), timeouts.assignment_seconds),
+            },
+            None if read_panel(&env, &job).is_empty() => (
+                env.storage().persistent().get(&DataKey::DisputedAt(job_id)),
+                timeouts.assignment_seconds,
+            ),
+            None => (assigned_at, timeouts.ruling_seconds),
+        };
+        let started_at = match started_at {
+            Some(started_at) if timeout > 0 => started_at,
+            _ => return Err(MarketError::DeadlineNotPassed),
+        };
+        let expires_at = started_at
+            .checked_add(timeout)
+            .ok_or(MarketError::Overflow)?;
+        if env.ledger().timestamp() <= expires_at {
+            return Err(MarketError::DeadlineNotPassed);
         }
 
-        let registry_client = registry::Client::new(&env, &registry_contract);
-        let profile = registry_client.get_profile(&artisan);
+        let (milestone, amount, finder_share, artisan_share) = match &ruling {
+            Some(ruling) => (
+                ruling.milestone,
+                ruling.amount,
+                ruling.finder_share,
+                ruling.artisan_share,
+            ),
+            None => {
+                let (milestone, amount) = disputed_amount(&env, &job)?;
+                let net = amount - platform_fee(&job, amount);
+                let finder_share = (net * (timeouts.default_finder_bps as i128)) / 10000;
+                (milestone, amount, finder_share, net - finder_share)
+            }
+        };
 
-        if profile.role != 3 {
-            panic!("User is not a verified Artisan");
-        }
-        if profile.is_blacklisted {
-            panic!("User is blacklisted");
+        env.storage().persistent().remove(&DataKey::Ruling(job_id));
+        settle_dispute(
+            &env,
+            &mut job,
+            milestone,
+            amount,
+            finder_share,
+            artisan_share,
+        )?;
+
+        if let Some(ruling) = ruling {
+            let appellant = ruling.appellant.ok_or(MarketError::NoRuling)?;
+            if ruling.bond > 0 {
+                let token_client = token::TokenClient::new(&env, &job.token);
+                token_client.transfer(&env.current_contract_address(), &appellant, &ruling.bond);
+            }
+
+            AppealDecided {
+                id: job_id,
+                appellant,
+                bond_refunded: true,
+            }
+            .publish(&env);
         }
 
-        let app_key = DataKey::Application(job_id, artisan.clone());
-        if env.storage().persistent().has(&app_key) {
-            panic!("Duplicate application");
+        DisputeExpired {
+            id: job_id,
+            finder_share,
+            artisan_share,
         }
+        .publish(&env);
 
-        let record = JobApplicationRecord {
-            job_id,
-            artisan: artisan.clone(),
-            applied_at: env.ledger().timestamp(),
-        };
+        Ok(())
+    }
 
-        env.storage().persistent().set(&app_key, &record);
-        env.storage()
-            .persistent()
-            .extend_ttl(&app_key, 100_000, 500_000);
+    /// Proposes a split of the disputed escrow to the other party, replacing
+    /// any earlier offer. Open before or after a panel is seated, but not
+    /// once a ruling is pending.
+    pub fn propose_settlement(
+        env: Env,
+        proposer: Address,
+        job_id: u64,
+        finder_share: i128,
+        artisan_share: i128,
+    ) -> Result<(), MarketError> {
+        proposer.require_auth();
 
-        let applicants_key = DataKey::JobApplicants(job_id);
-        let mut applicants: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&applicants_key)
-            .unwrap_or_else(|| Vec::new(&env));
-        applicants.push_back(artisan.clone());
-        env.storage().persistent().set(&applicants_key, &applicants);
+        let job = read_job(&env, job_id)?;
+        if job.finder != proposer && job.artisan.as_ref() != Some(&proposer) {
+            return Err(MarketError::NotJobParty);
+        }
+        if job.status != JobStatus::Disputed {
+            return Err(MarketError::JobNotDisputed);
+        }
+        if read_ruling(&env, job_id).is_some() {
+            return Err(MarketError::RulingPending);
+        }
+
+        let (_, amount) = disputed_amount(&env, &job)?;
+        validate_split(&job, amount, finder_share, artisan_share)?;
+
+        let key = DataKey::Settlement(job_id);
+        env.storage().persistent().set(
+            &key,
+            &SettlementOffer {
+                proposer: proposer.clone(),
+                finder_share,
+                artisan_share,
+                proposed_at: env.ledger().timestamp(),
+            },
+        );
         env.storage()
             .persistent()
-            .extend_ttl(&applicants_key, 100_000, 500_000);
+            .extend_ttl(&key, 100_000, 500_000);
 
-        JobApplication {
+        SettlementProposed {
             id: job_id,
-            artisan,
+            proposer,
+            finder_share,
+            artisan_share,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn start_job(env: Env, artisan: Address, job_id: u64) {
-        assert!(!is_paused(&env), "Contract Paused");
-        artisan.require_auth();
+    /// Accepts the other party's settlement offer, paying it out with the
+    /// same fees as a panel decision and closing the dispute.
+    pub fn accept_settlement(env: Env, party: Address, job_id: u64) -> Result<(), MarketError> {
+        party.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+        if job.finder != party && job.artisan.as_ref() != Some(&party) {
+            return Err(MarketError::NotJobParty);
+        }
+        if job.status != JobStatus::Disputed {
+            return Err(MarketError::JobNotDisputed);
+        }
+        if read_ruling(&env, job_id).is_some() {
+            return Err(MarketError::RulingPending);
+        }
 
-        let mut job: Job = env
+        let offer: SettlementOffer = env
             .storage()
             .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
-
-        if job.status != JobStatus::Assigned {
-            panic!("Job is not assigned");
+            .get(&DataKey::Settlement(job_id))
+            .ok_or(MarketError::OfferNotFound)?;
+        if offer.proposer == party {
+            return Err(MarketError::Unauthorized);
         }
 
-        if job.artisan != Some(artisan.clone()) {
-            panic!("Not assigned to this job");
+        let (milestone, amount) = disputed_amount(&env, &job)?;
+        settle_dispute(
+            &env,
+            &mut job,
+            milestone,
+            amount,
+            offer.finder_share,
+            offer.artisan_share,
+        )?;
+
+        SettlementAccepted {
+            id: job_id,
+            accepted_by: party,
         }
+        .publish(&env);
 
-        job.status = JobStatus::InProgress;
-        job.start_time = env.ledger().timestamp();
+        Ok(())
+    }
+
+    pub fn get_settlement(env: Env, job_id: u64) -> Option<SettlementOffer> {
+        env.storage().persistent().get(&DataKey::Settlement(job_id))
+    }
+
+    /// Sets the share of a disputed job's platform fee, in basis points,
+    /// paid to the jurors who voted on the dispute.
+    pub fn set_juror_reward(env: Env, admin: Address, share_bps: u32) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        if share_bps > 10000 {
+            return Err(MarketError::InvalidInput);
+        }
 
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
         env.storage()
-            .persistent()
-            .remove(&DataKey::AssignmentTime(job_id));
+            .instance()
+            .set(&DataKey::JurorRewardBps, &share_bps);
+
+        JurorRewardUpdated { share_bps }.publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_juror_reward(env: Env) -> u32 {
+        read_juror_reward_bps(&env)
+    }
+
+    /// Allowlists a SEP-41 token for escrow, or updates its job amount
+    /// limits if it is already allowed.
+    pub fn add_allowed_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+        min_amount: Option<i128>,
+        max_amount: Option<i128>,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        if let (Some(min), Some(max)) = (min_amount, max_amount) {
+            if min > max {
+                return Err(MarketError::InvalidInput);
+            }
+        }
+
+        let mut allowed = read_allowed_tokens(&env);
+        allowed.set(
+            token.clone(),
+            TokenLimits {
+                min_amount,
+                max_amount,
+            },
+        );
         env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+            .instance()
+            .set(&DataKey::AllowedTokens, &allowed);
 
-        JobStarted {
-            id: job_id,
-            artisan,
+        TokenAllowed {
+            token,
+            min_amount,
+            max_amount,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn cancel_job(env: Env, finder: Address, job_id: u64) {
-        assert!(!is_paused(&env), "Contract Paused");
-        finder.require_auth();
+    /// Stops new escrow in `token`. Jobs already funded with it are
+    /// unaffected, except that their budget can no longer be increased.
+    pub fn remove_allowed_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        let mut allowed = read_allowed_tokens(&env);
+        if allowed.remove(token.clone()).is_none() {
+            return Err(MarketError::TokenNotAllowed);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowedTokens, &allowed);
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+        TokenRemoved { token }.publish(&env);
 
-        if job.finder != finder {
-            panic!("Not job owner");
-        }
+        Ok(())
+    }
 
-        if job.status != JobStatus::Open {
-            panic!("Job is not open");
+    pub fn get_allowed_tokens(env: Env) -> Vec<Address> {
+        read_allowed_tokens(&env).keys()
+    }
+
+    pub fn get_token_limits(env: Env, token: Address) -> Option<TokenLimits> {
+        read_allowed_tokens(&env).get(token)
+    }
+
+    pub fn assign_juror(
+        env: Env,
+        admin: Address,
+        job_id: u64,
+        juror: Address,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.status != JobStatus::Disputed {
+            return Err(MarketError::JobNotDisputed);
         }
 
-        let token_client = token::TokenClient::new(&env, &job.token);
-        token_client.transfer(&env.current_contract_address(), &finder, &job.amount);
+        let profile = read_profile(&env, &juror)?;
 
-        job.status = JobStatus::Cancelled;
+        if profile.role != 1 {
+            return Err(MarketError::NotCurator);
+        }
 
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+        seat_panel(&env, &mut job, &Vec::from_array(&env, [juror.clone()]))?;
 
-        JobCancelled { id: job_id }.publish(&env);
+        JurorAssigned { id: job_id, juror }.publish(&env);
+
+        Ok(())
     }
 
-    pub fn complete_job(env: Env, artisan: Address, job_id: u64) {
-        assert!(!is_paused(&env), "Contract Paused");
-        artisan.require_auth();
+    /// Seats an odd-sized panel of distinct curators on a disputed job,
+    /// replacing any previous panel and its votes. Each juror proposes a
+    /// split through `resolve_dispute` or `resolve_milestone_dispute`.
+    pub fn assign_jury(
+        env: Env,
+        admin: Address,
+        job_id: u64,
+        jurors: Vec<Address>,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+        let mut job = read_job(&env, job_id)?;
 
-        if job.artisan != Some(artisan.clone()) {
-            panic!("Not assigned to this job");
+        if job.status != JobStatus::Disputed {
+            return Err(MarketError::JobNotDisputed);
         }
-
-        if job.status != JobStatus::InProgress {
-            panic!("Job is not in progress");
+        if jurors.is_empty() || jurors.len() > MAX_PANEL_SIZE || jurors.len().is_multiple_of(2) {
+            return Err(MarketError::InvalidInput);
+        }
+        for (i, juror) in jurors.iter().enumerate() {
+            if jurors.first_index_of(&juror) != Some(i as u32) {
+                return Err(MarketError::InvalidInput);
+            }
+            if read_profile(&env, &juror)?.role != 1 {
+                return Err(MarketError::NotCurator);
+            }
         }
 
-        job.status = JobStatus::PendingReview;
-        job.end_time = env.ledger().timestamp();
+        seat_panel(&env, &mut job, &jurors)?;
 
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+        PanelAssigned { id: job_id, jurors }.publish(&env);
+
+        Ok(())
+    }
 
-        JobCompleted {
-            id: job_id,
-            artisan,
+    pub fn get_panel(env: Env, job_id: u64) -> Vec<Address> {
+        match read_job(&env, job_id) {
+            Ok(job) => read_panel(&env, &job),
+            Err(_) => Vec::new(&env),
         }
-        .publish(&env);
     }
 
-    pub fn confirm_delivery(env: Env, finder: Address, job_id: u64) {
-        assert!(!is_paused(&env), "Contract Paused");
-        finder.require_auth();
+    pub fn get_votes(env: Env, job_id: u64) -> Vec<JurorVote> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Votes(job_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
 
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+    /// Appends `evidence_hash` to the job's evidence log. Either party or the
+    /// assigned juror may submit while the job is disputed, until
+    /// `EVIDENCE_WINDOW_SECONDS` after the juror was assigned.
+    pub fn submit_evidence(
+        env: Env,
+        job_id: u64,
+        party: Address,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), MarketError> {
+        party.require_auth();
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+        let job = read_job(&env, job_id)?;
 
-        if job.finder != finder {
-            panic!("Not job owner");
+        if job.status != JobStatus::Disputed {
+            return Err(MarketError::JobNotDisputed);
         }
-
-        if job.status != JobStatus::PendingReview {
-            panic!("Job is not pending review");
+        let panel = read_panel(&env, &job);
+        if job.finder != party && job.artisan.as_ref() != Some(&party) && !panel.contains(&party) {
+            return Err(MarketError::NotJobParty);
         }
 
-        let artisan = job.artisan.clone().expect("Job has no assigned artisan");
-
-        let fee_bps: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlatformFee)
-            .unwrap_or(100);
-        let fee = (job.amount * (fee_bps as i128)) / 10000;
-        let payout = job.amount - fee;
+        if !panel.is_empty() {
+            // A juror seated before the evidence window existed has no
+            // seating time, so the window stays open.
+            let assigned_at: Option<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::JurorAssignedAt(job_id));
+            if let Some(assigned_at) = assigned_at {
+                let closes_at = assigned_at
+                    .checked_add(EVIDENCE_WINDOW_SECONDS)
+                    .ok_or(MarketError::Overflow)?;
+                if env.ledger().timestamp() > closes_at {
+                    return Err(MarketError::WindowClosed);
+                }
+            }
+        }
 
-        let token_client = token::TokenClient::new(&env, &job.token);
-        let contract = env.current_contract_address();
-        token_client.transfer(&contract, &artisan, &payout);
-        token_client.transfer(&contract, &admin, &fee);
+        append_evidence(&env, job_id, party, evidence_hash)
+    }
 
-        job.status = JobStatus::Completed;
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
+    /// Returns the job's evidence log in submission order.
+    pub fn get_evidence(env: Env, job_id: u64) -> Vec<Evidence> {
         env.storage()
             .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
-
-        FundsReleased {
-            id: job_id,
-            artisan,
-            amount: payout,
-        }
-        .publish(&env);
+            .get(&DataKey::Evidence(job_id))
+            .unwrap_or_else(|| Vec::new(&env))
     }
 
-    pub fn raise_dispute(env: Env, caller: Address, job_id: u64) {
-        caller.require_auth();
+    /// Casts `juror`'s vote on how to split a disputed job's escrow. The
+    /// escrow is paid out once the panel's vote is decided.
+    pub fn resolve_dispute(
+        env: Env,
+        juror: Address,
+        job_id: u64,
+        finder_share: i128,
+        artisan_share: i128,
+    ) -> Result<(), MarketError> {
+        juror.require_auth();
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+        let mut job = read_job(&env, job_id)?;
 
-        if job.finder != caller && job.artisan.as_ref() != Some(&caller) {
-            panic!("Only the finder or assigned artisan can raise a dispute");
+        if job.status != JobStatus::Disputed {
+            return Err(MarketError::JobNotDisputed);
         }
+        require_single_payment_job(&env, job_id)?;
 
-        if job.status != JobStatus::InProgress && job.status != JobStatus::PendingReview {
-            panic!("Job cannot be disputed in its current status");
+        let (finder_share, artisan_share) =
+            match cast_vote(&env, &job, &juror, job.amount, finder_share, artisan_share)? {
+                Some(split) => split,
+                None => return Ok(()),
+            };
+
+        let amount = job.amount;
+        apply_panel_decision(&env, &mut job, None, amount, finder_share, artisan_share)
+    }
+
+    /// Sets the appeal period and bond, in basis points of the disputed
+    /// amount, for future rulings.
+    pub fn set_appeal_config(
+        env: Env,
+        admin: Address,
+        period_seconds: u64,
+        bond_bps: u32,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+
+        if bond_bps > 10000 {
+            return Err(MarketError::InvalidInput);
         }
 
-        job.status = JobStatus::Disputed;
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+        env.storage().instance().set(
+            &DataKey::AppealConfig,
+            &AppealConfig {
+                period_seconds,
+                bond_bps,
+            },
+        );
 
-        DisputeRaised {
-            id: job_id,
-            raised_by: caller,
+        AppealConfigUpdated {
+            period_seconds,
+            bond_bps,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn auto_release_funds(env: Env, artisan: Address, job_id: u64) {
-        assert!(!is_paused(&env), "Contract Paused");
-        artisan.require_auth();
+    pub fn get_appeal_config(env: Env) -> AppealConfig {
+        read_appeal_config(&env)
+    }
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+    pub fn get_ruling(env: Env, job_id: u64) -> Option<Ruling> {
+        read_ruling(&env, job_id)
+    }
 
-        if job.status != JobStatus::PendingReview {
-            panic!("Job is not in PendingReview status");
-        }
+    /// Appeals a pending ruling within the appeal window by posting the
+    /// appeal bond. The admin then seats a larger panel or decides the
+    /// appeal directly with `decide_appeal`. If neither happens within the
+    /// dispute timeouts, `expire_dispute` lets the ruling stand.
+    pub fn appeal_ruling(env: Env, appellant: Address, job_id: u64) -> Result<(), MarketError> {
+        appellant.require_auth();
 
-        let artisan_from_job = job.artisan.as_ref().expect("Job has no assigned artisan");
-        if artisan_from_job != &artisan {
-            panic!("Only the assigned artisan can release funds");
+        let job = read_job(&env, job_id)?;
+        if job.finder != appellant && job.artisan.as_ref() != Some(&appellant) {
+            return Err(MarketError::NotJobParty);
         }
 
-        let current_time = env.ledger().timestamp();
-        let seven_days_in_seconds: u64 = 604800;
-        let release_time = job.end_time + seven_days_in_seconds;
-
-        if current_time <= release_time {
-            panic!("7 days have not passed since job completion");
+        let mut ruling = read_ruling(&env, job_id).ok_or(MarketError::NoRuling)?;
+        if ruling.appellant.is_some() {
+            return Err(MarketError::AlreadySubmitted);
+        }
+        let closes_at = ruling
+            .ruled_at
+            .checked_add(read_appeal_config(&env).period_seconds)
+            .ok_or(MarketError::Overflow)?;
+        if env.ledger().timestamp() > closes_at {
+            return Err(MarketError::WindowClosed);
         }
 
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-
-        let fee_bps: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlatformFee)
-            .unwrap_or(100);
-        let fee = (job.amount * (fee_bps as i128)) / 10000;
-        let payout = job.amount - fee;
-
-        let token_client = token::TokenClient::new(&env, &job.token);
-        let contract = env.current_contract_address();
-        token_client.transfer(&contract, &artisan, &payout);
-        if fee > 0 {
-            token_client.transfer(&contract, &admin, &fee);
+        let bond = (ruling.amount * (read_appeal_config(&env).bond_bps as i128)) / 10000;
+        if bond > 0 {
+            let token_client = token::TokenClient::new(&env, &job.token);
+            token_client.transfer(&appellant, env.current_contract_address(), &bond);
         }
 
-        job.status = JobStatus::Completed;
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+        ruling.appellant = Some(appellant.clone());
+        ruling.bond = bond;
+        ruling.appealed_at = env.ledger().timestamp();
+        write_ruling(&env, job_id, &ruling);
 
-        FundsReleased {
+        RulingAppealed {
             id: job_id,
-            artisan,
-            amount: payout,
+            appellant,
+            bond,
         }
         .publish(&env);
-    }
 
-    pub fn extend_deadline(env: Env, finder: Address, job_id: u64, extra_time: u64) {
-        assert!(!is_paused(&env), "Contract Paused");
-        finder.require_auth();
+        Ok(())
+    }
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+    /// Pays out a ruling whose appeal window closed without an appeal.
+    pub fn finalize_ruling(env: Env, job_id: u64) -> Result<(), MarketError> {
+        let mut job = read_job(&env, job_id)?;
 
-        if job.finder != finder {
-            panic!("Not job owner");
+        let ruling = read_ruling(&env, job_id).ok_or(MarketError::NoRuling)?;
+        if ruling.appellant.is_some() {
+            return Err(MarketError::RulingPending);
         }
-
-        if job.status == JobStatus::Completed || job.status == JobStatus::Cancelled {
-            panic!("Job is already finalized");
+        let closes_at = ruling
+            .ruled_at
+            .checked_add(read_appeal_config(&env).period_seconds)
+            .ok_or(MarketError::Overflow)?;
+        if env.ledger().timestamp() <= closes_at {
+            return Err(MarketError::DeadlineNotPassed);
         }
 
-        job.deadline += extra_time;
+        env.storage().persistent().remove(&DataKey::Ruling(job_id));
+        settle_dispute(
+            &env,
+            &mut job,
+            ruling.milestone,
+            ruling.amount,
+            ruling.finder_share,
+            ruling.artisan_share,
+        )
+    }
 
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+    /// Lets the admin decide an appealed ruling directly.
+    pub fn decide_appeal(
+        env: Env,
+        admin: Address,
+        job_id: u64,
+        finder_share: i128,
+        artisan_share: i128,
+    ) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
 
-        DeadlineExtended {
-            id: job_id,
-            extra_time,
-            new_deadline: job.deadline,
+        let mut job = read_job(&env, job_id)?;
+        let ruling = read_ruling(&env, job_id).ok_or(MarketError::NoRuling)?;
+        if ruling.appellant.is_none() {
+            return Err(MarketError::RulingPending);
         }
-        .publish(&env);
+        validate_split(&job, ruling.amount, finder_share, artisan_share)?;
+
+        settle_appeal(&env, &mut job, ruling, finder_share, artisan_share)
     }
 
-    pub fn increase_budget(env: Env, finder: Address, job_id: u64, added_amount: i128) {
-        assert!(!is_paused(&env), "Contract Paused");
-        finder.require_auth();
+    /// Records a 1-5 `rating` and review metadata hash from one party of a
+    /// completed job for the other. Each party may review once per job.
+    pub fn leave_review(
+        env: Env,
+        reviewer: Address,
+        job_id: u64,
+        rating: u32,
+        review_hash: BytesN<32>,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        reviewer.require_auth();
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+        let job = read_job(&env, job_id)?;
 
-        if job.finder != finder {
-            panic!("Not job owner");
+        if job.status != JobStatus::Completed {
+            return Err(MarketError::JobNotCompleted);
         }
+        let artisan = job.artisan.clone().ok_or(MarketError::JobNotAssigned)?;
+        let reviewee = if reviewer == job.finder {
+            artisan
+        } else if reviewer == artisan {
+            job.finder.clone()
+        } else {
+            return Err(MarketError::NotJobParty);
+        };
 
-        if job.status == JobStatus::Completed || job.status == JobStatus::Cancelled {
-            panic!("Job is already finalized");
+        if !(1..=5).contains(&rating) {
+            return Err(MarketError::InvalidInput);
         }
 
-        let token_client = token::TokenClient::new(&env, &job.token);
-        token_client.transfer(&finder, env.current_contract_address(), &added_amount);
-
-        job.amount += added_amount;
+        let key = DataKey::Review(job_id, reviewer.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(MarketError::AlreadySubmitted);
+        }
 
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
+        let review = Review {
+            job_id,
+            reviewer: reviewer.clone(),
+            reviewee: reviewee.clone(),
+            rating,
+            review_hash,
+            created_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &review);
         env.storage()
             .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+            .extend_ttl(&key, 100_000, 500_000);
 
-        BudgetIncreased {
+        add_rating(&env, &reviewee, rating)?;
+        report_reputation(&env, &reviewee, registry::ReputationUpdate::Rated(rating));
+
+        ReviewSubmitted {
             id: job_id,
-            added_amount,
-            new_amount: job.amount,
+            reviewer,
+            reviewee,
+            rating,
         }
         .publish(&env);
-    }
 
-    pub fn transfer_admin(env: Env, old_admin: Address, new_admin: Address) {
-        assert!(!is_paused(&env), "Contract Paused");
-        old_admin.require_auth();
-
-        let current_admin = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        assert!(old_admin == current_admin, "Unauthorized caller");
+        Ok(())
+    }
 
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    pub fn get_review(env: Env, job_id: u64, reviewer: Address) -> Option<Review> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Review(job_id, reviewer))
+    }
 
-        AdminTransferred { new_admin }.publish(&env);
+    pub fn get_reputation(env: Env, user: Address) -> Reputation {
+        read_reputation(&env, &user)
     }
 
-    pub fn toggle_contract_pause(env: Env, admin: Address) {
-        admin.require_auth();
+    pub fn submit_milestone(
+        env: Env,
+        artisan: Address,
+        job_id: u64,
+        index: u32,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
 
-        let current_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        assert!(admin == current_admin, "Unauthorized caller");
+        let job = read_job(&env, job_id)?;
+        let mut milestones = read_milestones(&env, job_id).ok_or(MarketError::NotMilestoneJob)?;
 
-        let mut paused = env
-            .storage()
-            .instance()
-            .get(&DataKey::IsPaused)
-            .expect("Pause state not set");
+        if job.artisan != Some(artisan.clone()) {
+            return Err(MarketError::NotAssignedArtisan);
+        }
+        if job.status != JobStatus::InProgress {
+            return Err(MarketError::JobNotInProgress);
+        }
 
-        if paused {
-            env.storage().instance().set(&DataKey::IsPaused, &false);
-            paused = false;
-        } else {
-            env.storage().instance().set(&DataKey::IsPaused, &true);
-            paused = true;
+        let mut milestone = milestones
+            .get(index)
+            .ok_or(MarketError::MilestoneNotFound)?;
+        if milestone.status != MilestoneStatus::Pending {
+            return Err(MarketError::InvalidMilestoneStatus);
+        }
+        if let Some(terms) = read_recurrence(&env, job_id) {
+            if env.ledger().timestamp() < cycle_start(&terms, &milestone) {
+                return Err(MarketError::InvalidMilestoneStatus);
+            }
         }
 
-        PauseStateChanged { paused }.publish(&env);
-    }
+        milestone.status = MilestoneStatus::Submitted;
+        milestone.submitted_at = env.ledger().timestamp();
+        milestones.set(index, milestone);
+        write_milestones(&env, job_id, &milestones);
 
-    pub fn emergency_withdraw(env: Env, admin: Address, token: Address, amount: i128, to: Address) {
-        admin.require_auth();
+        MilestoneSubmitted { id: job_id, index }.publish(&env);
 
-        let current_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        assert!(admin == current_admin, "Unauthorized caller");
+        Ok(())
+    }
 
-        assert!(is_paused(&env), "Contract is not paused");
+    /// Releases a single milestone to the artisan. The finder may release a
+    /// milestone before it has been submitted.
+    pub fn release_milestone(
+        env: Env,
+        finder: Address,
+        job_id: u64,
+        index: u32,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        finder.require_auth();
 
-        let token_client = token::TokenClient::new(&env, &token);
-        token_client.transfer(&env.current_contract_address(), &to, &amount);
+        let mut job = read_job(&env, job_id)?;
+        let mut milestones = read_milestones(&env, job_id).ok_or(MarketError::NotMilestoneJob)?;
 
-        EmergencyWithdraw { token, amount, to }.publish(&env);
-    }
+        if job.finder != finder {
+            return Err(MarketError::NotJobOwner);
+        }
+        if job.status != JobStatus::InProgress {
+            return Err(MarketError::JobNotInProgress);
+        }
 
-    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
-        admin.require_auth();
+        let mut milestone = milestones
+            .get(index)
+            .ok_or(MarketError::MilestoneNotFound)?;
+        if milestone.status != MilestoneStatus::Pending
+            && milestone.status != MilestoneStatus::Submitted
+        {
+            return Err(MarketError::InvalidMilestoneStatus);
+        }
 
-        let current_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        assert!(admin == current_admin, "Unauthorized caller");
+        let artisan = job.artisan.clone().ok_or(MarketError::JobNotAssigned)?;
+        let payout = release_to_artisan(&env, &job, &artisan, milestone.amount)?;
 
-        env.deployer()
-            .update_current_contract_wasm(new_wasm_hash.clone());
+        milestone.status = MilestoneStatus::Released;
+        milestones.set(index, milestone);
+        write_milestones(&env, job_id, &milestones);
+        settle_if_all_milestones_done(&env, &mut job, &milestones);
+        write_job(&env, &job);
 
-        ContractUpgraded {
-            hash: new_wasm_hash,
+        MilestoneReleased {
+            id: job_id,
+            index,
+            artisan,
+            amount: payout,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn set_platform_fee(env: Env, admin: Address, fee_bps: u32) {
-        admin.require_auth();
+    /// Lets the artisan release a submitted milestone the finder has not acted
+    /// on within `AUTO_RELEASE_SECONDS`.
+    pub fn auto_release_milestone(
+        env: Env,
+        artisan: Address,
+        job_id: u64,
+        index: u32,
+    ) -> Result<(), MarketError> {
+        require_not_paused(&env)?;
+        artisan.require_auth();
 
-        let current_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        assert!(admin == current_admin, "Unauthorized caller");
+        let mut job = read_job(&env, job_id)?;
+        let mut milestones = read_milestones(&env, job_id).ok_or(MarketError::NotMilestoneJob)?;
 
-        assert!(fee_bps <= 1000, "Fee exceeds maximum allowed (1000 bps)");
+        if job.artisan != Some(artisan.clone()) {
+            return Err(MarketError::NotAssignedArtisan);
+        }
+        if job.status != JobStatus::InProgress {
+            return Err(MarketError::JobNotInProgress);
+        }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformFee, &fee_bps);
+        let mut milestone = milestones
+            .get(index)
+            .ok_or(MarketError::MilestoneNotFound)?;
+        if milestone.status != MilestoneStatus::Submitted {
+            return Err(MarketError::InvalidMilestoneStatus);
+        }
+        if env.ledger().timestamp() <= milestone.submitted_at + AUTO_RELEASE_SECONDS {
+            return Err(MarketError::ReviewPeriodActive);
+        }
 
-        FeeUpdated {
-            new_fee_bps: fee_bps,
+        let payout = release_to_artisan(&env, &job, &artisan, milestone.amount)?;
+
+        milestone.status = MilestoneStatus::Released;
+        milestones.set(index, milestone);
+        write_milestones(&env, job_id, &milestones);
+        settle_if_all_milestones_done(&env, &mut job, &milestones);
+        write_job(&env, &job);
+
+        MilestoneReleased {
+            id: job_id,
+            index,
+            artisan,
+            amount: payout,
         }
         .publish(&env);
-    }
-
-    pub fn assign_juror(env: Env, admin: Address, job_id: u64, juror: Address) {
-        admin.require_auth();
 
-        let current_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        assert!(admin == current_admin, "Unauthorized caller");
+        Ok(())
+    }
 
-        let registry_contract: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::RegistryContract)
-            .expect("Contract not initialized");
+    /// Disputes a single milestone. The job moves to `Disputed` until a juror
+    /// assigned through `assign_juror` resolves that milestone.
+    pub fn dispute_milestone(
+        env: Env,
+        caller: Address,
+        job_id: u64,
+        index: u32,
+    ) -> Result<(), MarketError> {
+        caller.require_auth();
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+        let mut job = read_job(&env, job_id)?;
+        let mut milestones = read_milestones(&env, job_id).ok_or(MarketError::NotMilestoneJob)?;
 
-        assert!(job.status == JobStatus::Disputed, "Job is not disputed");
+        if job.finder != caller && job.artisan.as_ref() != Some(&caller) {
+            return Err(MarketError::NotJobParty);
+        }
+        if job.status != JobStatus::InProgress {
+            return Err(MarketError::JobNotDisputable);
+        }
 
-        let registry_client = registry::Client::new(&env, &registry_contract);
-        let profile = registry_client.get_profile(&juror);
+        let mut milestone = milestones
+            .get(index)
+            .ok_or(MarketError::MilestoneNotFound)?;
+        if milestone.status != MilestoneStatus::Pending
+            && milestone.status != MilestoneStatus::Submitted
+        {
+            return Err(MarketError::InvalidMilestoneStatus);
+        }
 
-        assert!(profile.role == 1, "User is not a Curator");
+        milestone.status = MilestoneStatus::Disputed;
+        milestones.set(index, milestone);
+        write_milestones(&env, job_id, &milestones);
+        open_dispute(&env, &mut job);
+        write_job(&env, &job);
 
-        job.juror = Some(juror.clone());
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+        MilestoneDisputed {
+            id: job_id,
+            index,
+            raised_by: caller,
+        }
+        .publish(&env);
 
-        JurorAssigned { id: job_id, juror }.publish(&env);
+        Ok(())
     }
 
-    pub fn resolve_dispute(
+    /// Splits a disputed milestone's escrow. The remaining milestones resume
+    /// once the dispute is settled.
+    pub fn resolve_milestone_dispute(
         env: Env,
         juror: Address,
         job_id: u64,
+        index: u32,
         finder_share: i128,
         artisan_share: i128,
-    ) {
+    ) -> Result<(), MarketError> {
         juror.require_auth();
 
-        let mut job: Job = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Job(job_id))
-            .expect("Job not found");
+        let mut job = read_job(&env, job_id)?;
+        let milestones = read_milestones(&env, job_id).ok_or(MarketError::NotMilestoneJob)?;
 
-        assert!(job.status == JobStatus::Disputed, "Job is not disputed");
-        assert!(job.juror == Some(juror.clone()), "Not assigned juror");
+        if job.status != JobStatus::Disputed {
+            return Err(MarketError::JobNotDisputed);
+        }
 
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+        let milestone = milestones
+            .get(index)
+            .ok_or(MarketError::MilestoneNotFound)?;
+        if milestone.status != MilestoneStatus::Disputed {
+            return Err(MarketError::InvalidMilestoneStatus);
+        }
 
-        let fee_bps: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlatformFee)
-            .unwrap_or(100);
-        let fee = (job.amount * (fee_bps as i128)) / 10000;
-        assert!(
-            finder_share + artisan_share + fee == job.amount,
-            "Invalid shares"
-        );
+        let (finder_share, artisan_share) = match cast_vote(
+            &env,
+            &job,
+            &juror,
+            milestone.amount,
+            finder_share,
+            artisan_share,
+        )? {
+            Some(split) => split,
+            None => return Ok(()),
+        };
 
-        let token_client = token::TokenClient::new(&env, &job.token);
-        let contract = env.current_contract_address();
+        apply_panel_decision(
+            &env,
+            &mut job,
+            Some(index),
+            milestone.amount,
+            finder_share,
+            artisan_share,
+        )
+    }
 
-        if finder_share > 0 {
-            token_client.transfer(&contract, &job.finder, &finder_share);
-        }
+    pub fn get_milestones(env: Env, job_id: u64) -> Result<Vec<Milestone>, MarketError> {
+        read_milestones(&env, job_id).ok_or(MarketError::NotMilestoneJob)
+    }
 
-        if artisan_share > 0 {
-            let artisan = job.artisan.clone().expect("Job has no assigned artisan");
-            token_client.transfer(&contract, &artisan, &artisan_share);
-        }
+    pub fn get_job(env: Env, job_id: u64) -> Result<Job, MarketError> {
+        let job = read_job(&env, job_id)?;
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::Job(job_id), 100_000, 500_000);
+        Ok(job)
+    }
 
-        if fee > 0 {
-            token_client.transfer(&contract, &admin, &fee);
-        }
+    /// Lists jobs currently in `status`, starting at position `cursor` in
+    /// the index (pass 0 for the first page, then the previous cursor plus
+    /// the number of jobs returned). Jobs are listed in the order they
+    /// entered the index, except that a job leaving it is replaced by the
+    /// last entry. At most `MAX_PAGE_SIZE` jobs are returned per call.
+    pub fn list_jobs(env: Env, status: JobStatus, cursor: u64, limit: u32) -> Vec<Job> {
+        read_job_page(&env, &JobIndex::Status(status), cursor, limit)
+    }
 
-        job.status = JobStatus::Completed;
-        env.storage().persistent().set(&DataKey::Job(job_id), &job);
+    pub fn jobs_by_finder(env: Env, finder: Address, cursor: u64, limit: u32) -> Vec<Job> {
+        read_job_page(&env, &JobIndex::Finder(finder), cursor, limit)
+    }
 
-        DisputeResolved {
-            id: job_id,
-            finder_share,
-            artisan_share,
-        }
-        .publish(&env);
+    /// Lists jobs whose current assigned artisan is `artisan`.
+    pub fn jobs_by_artisan(env: Env, artisan: Address, cursor: u64, limit: u32) -> Vec<Job> {
+        read_job_page(&env, &JobIndex::Artisan(artisan), cursor, limit)
     }
 
     pub fn get_job_applicants(env: Env, job_id: u64) -> Vec<Address> {
@@ -1,6 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractevent, contractimpl, contracttype, Address, BytesN, Env, String,
+    contract, contracterror, contractevent, contractimpl, contracttype, Address, BytesN, Env,
+    String,
 };
 
 pub const ROLE_FINDER: u32 = 0;
@@ -8,13 +9,72 @@ pub const ROLE_CURATOR: u32 = 1;
 pub const ROLE_ADMIN: u32 = 2;
 pub const ROLE_ARTISAN: u32 = 3;
 
-#[derive(Clone)]
+/// Stable error codes returned by every fallible registry entrypoint.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RegistryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    UserAlreadyRegistered = 4,
+    UserNotFound = 5,
+    CallerNotRegistered = 6,
+    AlreadyCurator = 7,
+    NotCurator = 8,
+    NotCuratorOrAdmin = 9,
+    MissingMetadata = 10,
+    ApplicationNotPending = 11,
+    AlreadyBlacklisted = 12,
+    NotBlacklisted = 13,
+    MarketNotAuthorized = 14,
+    InvalidRating = 15,
+}
+
+/// Track record aggregated from every authorised market.
+/// `average_rating` is in hundredths, e.g. 450 for 4.5 stars.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct ReputationStats {
+    pub completed_jobs: u32,
+    pub disputes_won: u32,
+    pub disputes_lost: u32,
+    pub jobs_abandoned: u32,
+    pub rating_count: u32,
+    pub rating_total: u64,
+    pub average_rating: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Profile {
     pub role: u32,
     pub metadata_hash: String,
     pub is_verified: bool,
     pub is_blacklisted: bool,
+    pub reputation: ReputationStats,
+}
+
+/// The profile fields stored under `DataKey::Profile`. Reputation is kept
+/// under its own key so profiles written before it existed still decode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct StoredProfile {
+    pub role: u32,
+    pub metadata_hash: String,
+    pub is_verified: bool,
+    pub is_blacklisted: bool,
+}
+
+/// A single change to a user's track record, reported by a market.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ReputationUpdate {
+    JobCompleted,
+    DisputeWon,
+    DisputeLost,
+    JobAbandoned,
+    Rated(u32),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -30,6 +90,8 @@ pub enum DataKey {
     Profile(Address),
     VerificationApplication(Address),
     Admin,
+    AuthorizedMarket(Address),
+    Reputation(Address),
 }
 
 #[contractevent]
@@ -76,6 +138,25 @@ pub struct UserUnblacklisted {
     pub user: Address,
 }
 
+#[contractevent]
+pub struct MarketAuthorized {
+    #[topic]
+    pub market: Address,
+}
+
+#[contractevent]
+pub struct MarketRevoked {
+    #[topic]
+    pub market: Address,
+}
+
+#[contractevent]
+pub struct ReputationUpdated {
+    #[topic]
+    pub user: Address,
+    pub update: ReputationUpdate,
+}
+
 #[contractevent]
 pub struct AdminTransferred {
     #[topic]
@@ -90,7 +171,7 @@ pub struct ContractUpgraded {
 #[contract]
 pub struct Registry;
 
-fn read_profile(env: &Env, user: &Address) -> Option<Profile> {
+fn read_profile(env: &Env, user: &Address) -> Option<StoredProfile> {
     let key = DataKey::Profile(user.clone());
     let profile = env.storage().persistent().get(&key);
     if profile.is_some() {
@@ -101,7 +182,7 @@ fn read_profile(env: &Env, user: &Address) -> Option<Profile> {
     profile
 }
 
-fn write_profile(env: &Env, user: &Address, profile: &Profile) {
+fn write_profile(env: &Env, user: &Address, profile: &StoredProfile) {
     let key = DataKey::Profile(user.clone());
     env.storage().persistent().set(&key, profile);
     env.storage()
@@ -109,6 +190,25 @@ fn write_profile(env: &Env, user: &Address, profile: &Profile) {
         .extend_ttl(&key, 100_000, 500_000);
 }
 
+fn read_reputation(env: &Env, user: &Address) -> ReputationStats {
+    let key = DataKey::Reputation(user.clone());
+    let stats = env.storage().persistent().get(&key);
+    if stats.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+    }
+    stats.unwrap_or_default()
+}
+
+fn write_reputation(env: &Env, user: &Address, stats: &ReputationStats) {
+    let key = DataKey::Reputation(user.clone());
+    env.storage().persistent().set(&key, stats);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, 100_000, 500_000);
+}
+
 fn read_verification_status(env: &Env, user: &Address) -> Option<VerificationStatus> {
     let key = DataKey::VerificationApplication(user.clone());
     let status = env.storage().persistent().get(&key);
@@ -128,6 +228,17 @@ fn write_verification_status(env: &Env, user: &Address, status: &VerificationSta
         .extend_ttl(&key, 100_000, 500_000);
 }
 
+fn is_authorized_market(env: &Env, market: &Address) -> bool {
+    let key = DataKey::AuthorizedMarket(market.clone());
+    let authorized = env.storage().persistent().get(&key).unwrap_or(false);
+    if authorized {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+    }
+    authorized
+}
+
 fn read_admin(env: &Env) -> Option<Address> {
     let admin = env.storage().instance().get(&DataKey::Admin);
     if admin.is_some() {
@@ -143,21 +254,26 @@ fn write_admin(env: &Env, admin: &Address) {
 
 #[contractimpl]
 impl Registry {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), RegistryError> {
         if read_admin(&env).is_some() {
-            panic!("Already initialized");
+            return Err(RegistryError::AlreadyInitialized);
         }
         write_admin(&env, &admin);
+        Ok(())
     }
 
-    pub fn register_user(env: Env, user: Address, metadata_hash: String) {
+    pub fn register_user(
+        env: Env,
+        user: Address,
+        metadata_hash: String,
+    ) -> Result<(), RegistryError> {
         user.require_auth();
 
         if read_profile(&env, &user).is_some() {
-            panic!("User already registered");
+            return Err(RegistryError::UserAlreadyRegistered);
         }
 
-        let profile = Profile {
+        let profile = StoredProfile {
             role: ROLE_FINDER,
             metadata_hash,
             is_verified: false,
@@ -171,15 +287,18 @@ impl Registry {
             role: ROLE_FINDER,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn update_profile_metadata(env: Env, user: Address, new_metadata_hash: String) {
+    pub fn update_profile_metadata(
+        env: Env,
+        user: Address,
+        new_metadata_hash: String,
+    ) -> Result<(), RegistryError> {
         user.require_auth();
 
-        let mut profile = match read_profile(&env, &user) {
-            Some(p) => p,
-            None => panic!("User not registered"),
-        };
+        let mut profile = read_profile(&env, &user).ok_or(RegistryError::UserNotFound)?;
 
         profile.metadata_hash = new_metadata_hash.clone();
         write_profile(&env, &user, &profile);
@@ -189,65 +308,66 @@ impl Registry {
             metadata_hash: new_metadata_hash,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn add_curator(env: Env, curator: Address) {
-        let admin = read_admin(&env).expect("Contract not initialized");
+    pub fn add_curator(env: Env, curator: Address) -> Result<(), RegistryError> {
+        let admin = read_admin(&env).ok_or(RegistryError::NotInitialized)?;
         admin.require_auth();
 
-        let mut profile = match read_profile(&env, &curator) {
-            Some(p) => p,
-            None => panic!("User not found"),
-        };
+        let mut profile = read_profile(&env, &curator).ok_or(RegistryError::UserNotFound)?;
 
         if profile.role == ROLE_CURATOR {
-            panic!("User is already a Curator");
+            return Err(RegistryError::AlreadyCurator);
         }
 
         profile.role = ROLE_CURATOR;
         write_profile(&env, &curator, &profile);
+
+        Ok(())
     }
 
-    pub fn remove_curator(env: Env, curator: Address) {
-        let admin = read_admin(&env).expect("Contract not initialized");
+    pub fn remove_curator(env: Env, curator: Address) -> Result<(), RegistryError> {
+        let admin = read_admin(&env).ok_or(RegistryError::NotInitialized)?;
         admin.require_auth();
 
-        let mut profile = match read_profile(&env, &curator) {
-            Some(p) => p,
-            None => panic!("User not found"),
-        };
+        let mut profile = read_profile(&env, &curator).ok_or(RegistryError::UserNotFound)?;
 
         if profile.role != ROLE_CURATOR {
-            panic!("User is not a Curator");
+            return Err(RegistryError::NotCurator);
         }
 
         profile.role = ROLE_FINDER;
         write_profile(&env, &curator, &profile);
 
         CuratorRemoved { curator }.publish(&env);
+
+        Ok(())
     }
 
-    pub fn get_profile(env: Env, user: Address) -> Profile {
-        match read_profile(&env, &user) {
-            Some(p) => p,
-            None => panic!("User not found"),
-        }
+    pub fn get_profile(env: Env, user: Address) -> Result<Profile, RegistryError> {
+        let profile = read_profile(&env, &user).ok_or(RegistryError::UserNotFound)?;
+        Ok(Profile {
+            role: profile.role,
+            metadata_hash: profile.metadata_hash,
+            is_verified: profile.is_verified,
+            is_blacklisted: profile.is_blacklisted,
+            reputation: read_reputation(&env, &user),
+        })
     }
 
-    pub fn get_admin(env: Env) -> Address {
-        read_admin(&env).expect("Contract not initialized")
+    pub fn get_admin(env: Env) -> Result<Address, RegistryError> {
+        read_admin(&env).ok_or(RegistryError::NotInitialized)
     }
 
-    pub fn apply_for_verification(env: Env, caller: Address) {
+    pub fn apply_for_verification(env: Env, caller: Address) -> Result<(), RegistryError> {
         caller.require_auth();
 
-        let profile = match read_profile(&env, &caller) {
-            Some(p) => p,
-            None => panic!("User not registered"),
-        };
+        let profile = read_profile(&env, &caller).ok_or(RegistryError::UserNotFound)?;
 
         if profile.metadata_hash.is_empty() {
-            panic!("Metadata hash is missing");
+            return Err(RegistryError::MissingMetadata);
         }
 
         write_verification_status(&env, &caller, &VerificationStatus::Pending);
@@ -256,32 +376,32 @@ impl Registry {
             user_address: caller,
         }
         .publish(&env);
+
+        Ok(())
     }
 
-    pub fn approve_artisan(env: Env, caller: Address, artisan: Address) {
+    pub fn approve_artisan(
+        env: Env,
+        caller: Address,
+        artisan: Address,
+    ) -> Result<(), RegistryError> {
         caller.require_auth();
 
-        let caller_profile = match read_profile(&env, &caller) {
-            Some(p) => p,
-            None => panic!("Caller not registered"),
-        };
+        let caller_profile =
+            read_profile(&env, &caller).ok_or(RegistryError::CallerNotRegistered)?;
 
         if caller_profile.role != ROLE_CURATOR && caller_profile.role != ROLE_ADMIN {
-            panic!("Caller must be Curator or Admin");
+            return Err(RegistryError::NotCuratorOrAdmin);
         }
 
-        let mut artisan_profile = match read_profile(&env, &artisan) {
-            Some(p) => p,
-            None => panic!("User not found"),
-        };
+        let mut artisan_profile =
+            read_profile(&env, &artisan).ok_or(RegistryError::UserNotFound)?;
 
-        let application_status = match read_verification_status(&env, &artisan) {
-            Some(status) => status,
-            None => panic!("Verification application is not pending"),
-        };
+        let application_status =
+            read_verification_status(&env, &artisan).ok_or(RegistryError::ApplicationNotPending)?;
 
         if application_status != VerificationStatus::Pending {
-            panic!("Verification application is not pending");
+            return Err(RegistryError::ApplicationNotPending);
         }
 
         artisan_profile.role = ROLE_ARTISAN;
@@ -290,66 +410,181 @@ impl Registry {
         write_verification_status(&env, &artisan, &VerificationStatus::Approved);
 
         UserVerified { artisan }.publish(&env);
+
+        Ok(())
     }
 
-    pub fn blacklist_user(env: Env, admin: Address, user: Address) {
+    pub fn blacklist_user(env: Env, admin: Address, user: Address) -> Result<(), RegistryError> {
         admin.require_auth();
 
-        let current_admin = read_admin(&env).expect("Contract not initialized");
-        assert!(admin == current_admin, "Unauthorized caller");
+        let current_admin = read_admin(&env).ok_or(RegistryError::NotInitialized)?;
+        if admin != current_admin {
+            return Err(RegistryError::Unauthorized);
+        }
 
-        let mut profile = match read_profile(&env, &user) {
-            Some(p) => p,
-            None => panic!("User not found"),
-        };
+        let mut profile = read_profile(&env, &user).ok_or(RegistryError::UserNotFound)?;
 
         if profile.is_blacklisted {
-            panic!("User is already blacklisted");
+            return Err(RegistryError::AlreadyBlacklisted);
         }
 
         profile.is_blacklisted = true;
         write_profile(&env, &user, &profile);
 
         UserBlacklisted { user }.publish(&env);
+
+        Ok(())
     }
 
-    pub fn unblacklist_user(env: Env, admin: Address, user: Address) {
+    pub fn unblacklist_user(env: Env, admin: Address, user: Address) -> Result<(), RegistryError> {
         admin.require_auth();
 
-        let current_admin = read_admin(&env).expect("Contract not initialized");
-        assert!(admin == current_admin, "Unauthorized caller");
+        let current_admin = read_admin(&env).ok_or(RegistryError::NotInitialized)?;
+        if admin != current_admin {
+            return Err(RegistryError::Unauthorized);
+        }
 
-        let mut profile = match read_profile(&env, &user) {
-            Some(p) => p,
-            None => panic!("User not found"),
-        };
+        let mut profile = read_profile(&env, &user).ok_or(RegistryError::UserNotFound)?;
 
         if !profile.is_blacklisted {
-            panic!("User is not blacklisted");
+            return Err(RegistryError::NotBlacklisted);
         }
 
         profile.is_blacklisted = false;
         write_profile(&env, &user, &profile);
 
         UserUnblacklisted { user }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Allows `market` to report job outcomes through `update_reputation`.
+    pub fn authorize_market(
+        env: Env,
+        admin: Address,
+        market: Address,
+    ) -> Result<(), RegistryError> {
+        admin.require_auth();
+
+        let current_admin = read_admin(&env).ok_or(RegistryError::NotInitialized)?;
+        if admin != current_admin {
+            return Err(RegistryError::Unauthorized);
+        }
+
+        let key = DataKey::AuthorizedMarket(market.clone());
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, 100_000, 500_000);
+
+        MarketAuthorized { market }.publish(&env);
+
+        Ok(())
     }
 
-    pub fn transfer_admin(env: Env, old_admin: Address, new_admin: Address) {
+    pub fn revoke_market(env: Env, admin: Address, market: Address) -> Result<(), RegistryError> {
+        admin.require_auth();
+
+        let current_admin = read_admin(&env).ok_or(RegistryError::NotInitialized)?;
+        if admin != current_admin {
+            return Err(RegistryError::Unauthorized);
+        }
+
+        if !is_authorized_market(&env, &market) {
+            return Err(RegistryError::MarketNotAuthorized);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AuthorizedMarket(market.clone()));
+
+        MarketRevoked { market }.publish(&env);
+
+        Ok(())
+    }
+
+    pub fn is_market_authorized(env: Env, market: Address) -> bool {
+        is_authorized_market(&env, &market)
+    }
+
+    /// Applies a job outcome reported by an authorised market contract to
+    /// the user's reputation.
+    pub fn update_reputation(
+        env: Env,
+        market: Address,
+        user: Address,
+        update: ReputationUpdate,
+    ) -> Result<(), RegistryError> {
+        market.require_auth();
+
+        if !is_authorized_market(&env, &market) {
+            return Err(RegistryError::MarketNotAuthorized);
+        }
+
+        if read_profile(&env, &user).is_none() {
+            return Err(RegistryError::UserNotFound);
+        }
+
+        let mut stats = read_reputation(&env, &user);
+        match update {
+            ReputationUpdate::JobCompleted => {
+                stats.completed_jobs = stats.completed_jobs.saturating_add(1);
+            }
+            ReputationUpdate::DisputeWon => {
+                stats.disputes_won = stats.disputes_won.saturating_add(1);
+            }
+            ReputationUpdate::DisputeLost => {
+                stats.disputes_lost = stats.disputes_lost.saturating_add(1);
+            }
+            ReputationUpdate::JobAbandoned => {
+                stats.jobs_abandoned = stats.jobs_abandoned.saturating_add(1);
+            }
+            ReputationUpdate::Rated(rating) => {
+                if !(1..=5).contains(&rating) {
+                    return Err(RegistryError::InvalidRating);
+                }
+                stats.rating_count = stats.rating_count.saturating_add(1);
+                stats.rating_total = stats.rating_total.saturating_add(rating as u64);
+                stats.average_rating =
+                    ((stats.rating_total * 100) / stats.rating_count as u64) as u32;
+            }
+        }
+        write_reputation(&env, &user, &stats);
+
+        ReputationUpdated { user, update }.publish(&env);
+
+        Ok(())
+    }
+
+    pub fn transfer_admin(
+        env: Env,
+        old_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), RegistryError> {
         old_admin.require_auth();
 
-        let current_admin = read_admin(&env).expect("No current admin");
-        assert!(old_admin == current_admin, "Unauthorized caller");
+        let current_admin = read_admin(&env).ok_or(RegistryError::NotInitialized)?;
+        if old_admin != current_admin {
+            return Err(RegistryError::Unauthorized);
+        }
 
         write_admin(&env, &new_admin);
 
         AdminTransferred { new_admin }.publish(&env);
+
+        Ok(())
     }
 
-    pub fn upgrade_contract_code(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+    pub fn upgrade_contract_code(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), RegistryError> {
         admin.require_auth();
 
-        let current_admin = read_admin(&env).expect("No current admin");
-        assert!(admin == current_admin, "Unauthorized caller");
+        let current_admin = read_admin(&env).ok_or(RegistryError::NotInitialized)?;
+        if admin != current_admin {
+            return Err(RegistryError::Unauthorized);
+        }
 
         env.deployer()
             .update_current_contract_wasm(new_wasm_hash.clone());
@@ -358,6 +593,8 @@ impl Registry {
             hash: new_wasm_hash,
         }
         .publish(&env);
+
+        Ok(())
     }
 }
 
@@ -16,7 +16,7 @@ fn seed_profile(env: &Env, contract_id: &Address, user: &Address, role: u32) {
         write_profile(
             env,
             user,
-            &Profile {
+            &StoredProfile {
                 role,
                 metadata_hash: String::from_str(env, "hash"),
                 is_verified: false,
@@ -83,14 +83,52 @@ fn test_register_user_success() {
 }
 
 #[test]
-#[should_panic(expected = "User already registered")]
 fn test_register_user_twice_fails() {
     let (env, _contract_id, client) = setup_env();
     let user = Address::generate(&env);
     env.mock_all_auths();
 
     client.register_user(&user, &String::from_str(&env, "hash1"));
-    client.register_user(&user, &String::from_str(&env, "hash2"));
+    assert_eq!(
+        client.try_register_user(&user, &String::from_str(&env, "hash2")),
+        Err(Ok(RegistryError::UserAlreadyRegistered))
+    );
+}
+
+#[test]
+fn test_initialize_twice_fails() {
+    let (env, _contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(RegistryError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_get_profile_unregistered_user_fails() {
+    let (env, _contract_id, client) = setup_env();
+    let ghost = Address::generate(&env);
+
+    assert_eq!(
+        client.try_get_profile(&ghost),
+        Err(Ok(RegistryError::UserNotFound))
+    );
+}
+
+#[test]
+fn test_apply_for_verification_requires_metadata() {
+    let (env, _contract_id, client) = setup_env();
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.register_user(&user, &String::from_str(&env, ""));
+    assert_eq!(
+        client.try_apply_for_verification(&user),
+        Err(Ok(RegistryError::MissingMetadata))
+    );
 }
 
 #[test]
@@ -110,7 +148,6 @@ fn test_remove_curator_demotes_curator_to_finder() {
 }
 
 #[test]
-#[should_panic(expected = "User not found")]
 fn test_remove_curator_panics_for_unregistered_user() {
     let (env, _contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -118,11 +155,13 @@ fn test_remove_curator_panics_for_unregistered_user() {
     env.mock_all_auths();
 
     client.initialize(&admin);
-    client.remove_curator(&ghost);
+    assert_eq!(
+        client.try_remove_curator(&ghost),
+        Err(Ok(RegistryError::UserNotFound))
+    );
 }
 
 #[test]
-#[should_panic(expected = "User is not a Curator")]
 fn test_remove_curator_panics_if_not_curator() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -132,7 +171,10 @@ fn test_remove_curator_panics_if_not_curator() {
     client.initialize(&admin);
     seed_profile(&env, &contract_id, &finder, ROLE_FINDER);
 
-    client.remove_curator(&finder);
+    assert_eq!(
+        client.try_remove_curator(&finder),
+        Err(Ok(RegistryError::NotCurator))
+    );
 }
 
 #[test]
@@ -154,7 +196,6 @@ fn test_remove_curator_does_not_affect_other_users() {
 }
 
 #[test]
-#[should_panic(expected = "User is not a Curator")]
 fn test_remove_curator_cannot_be_called_twice() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -164,11 +205,13 @@ fn test_remove_curator_cannot_be_called_twice() {
     client.initialize(&admin);
     seed_profile(&env, &contract_id, &curator, ROLE_CURATOR);
     client.remove_curator(&curator);
-    client.remove_curator(&curator);
+    assert_eq!(
+        client.try_remove_curator(&curator),
+        Err(Ok(RegistryError::NotCurator))
+    );
 }
 
 #[test]
-#[should_panic(expected = "User is not a Curator")]
 fn test_remove_curator_cannot_demote_admin() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -176,7 +219,10 @@ fn test_remove_curator_cannot_demote_admin() {
 
     client.initialize(&admin);
     seed_profile(&env, &contract_id, &admin, ROLE_ADMIN);
-    client.remove_curator(&admin);
+    assert_eq!(
+        client.try_remove_curator(&admin),
+        Err(Ok(RegistryError::NotCurator))
+    );
 }
 
 #[test]
@@ -225,7 +271,6 @@ fn test_approve_artisan_by_admin() {
 }
 
 #[test]
-#[should_panic(expected = "Verification application is not pending")]
 fn test_approve_artisan_requires_pending_application() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -237,11 +282,13 @@ fn test_approve_artisan_requires_pending_application() {
     seed_profile(&env, &contract_id, &curator, ROLE_CURATOR);
     seed_profile(&env, &contract_id, &finder, ROLE_FINDER);
 
-    client.approve_artisan(&curator, &finder);
+    assert_eq!(
+        client.try_approve_artisan(&curator, &finder),
+        Err(Ok(RegistryError::ApplicationNotPending))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Caller must be Curator or Admin")]
 fn test_approve_artisan_panics_when_called_by_finder() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -253,11 +300,13 @@ fn test_approve_artisan_panics_when_called_by_finder() {
     seed_profile(&env, &contract_id, &finder1, ROLE_FINDER);
     seed_profile(&env, &contract_id, &finder2, ROLE_FINDER);
 
-    client.approve_artisan(&finder1, &finder2);
+    assert_eq!(
+        client.try_approve_artisan(&finder1, &finder2),
+        Err(Ok(RegistryError::NotCuratorOrAdmin))
+    );
 }
 
 #[test]
-#[should_panic(expected = "User not found")]
 fn test_approve_artisan_panics_for_unregistered_user() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -268,7 +317,10 @@ fn test_approve_artisan_panics_for_unregistered_user() {
     client.initialize(&admin);
     seed_profile(&env, &contract_id, &curator, ROLE_CURATOR);
 
-    client.approve_artisan(&curator, &ghost);
+    assert_eq!(
+        client.try_approve_artisan(&curator, &ghost),
+        Err(Ok(RegistryError::UserNotFound))
+    );
 }
 
 #[test]
@@ -299,7 +351,6 @@ fn test_approve_artisan_does_not_affect_other_users() {
 }
 
 #[test]
-#[should_panic(expected = "Verification application is not pending")]
 fn test_approve_artisan_cannot_be_called_twice_after_approval() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -315,7 +366,10 @@ fn test_approve_artisan_cannot_be_called_twice_after_approval() {
     client.approve_artisan(&curator, &finder);
     assert_eq!(client.get_profile(&finder).role, ROLE_ARTISAN);
 
-    client.approve_artisan(&curator, &finder);
+    assert_eq!(
+        client.try_approve_artisan(&curator, &finder),
+        Err(Ok(RegistryError::ApplicationNotPending))
+    );
 }
 
 #[test]
@@ -334,6 +388,40 @@ fn test_add_curator_by_admin() {
     assert_eq!(profile_after.role, ROLE_CURATOR);
 }
 
+#[test]
+fn test_add_curator_twice_fails() {
+    let (env, contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let curator = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    seed_profile(&env, &contract_id, &curator, ROLE_CURATOR);
+
+    assert_eq!(
+        client.try_add_curator(&curator),
+        Err(Ok(RegistryError::AlreadyCurator))
+    );
+}
+
+#[test]
+fn test_approve_artisan_by_unregistered_caller_fails() {
+    let (env, contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let ghost = Address::generate(&env);
+    let finder = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    seed_profile(&env, &contract_id, &finder, ROLE_FINDER);
+    client.apply_for_verification(&finder);
+
+    assert_eq!(
+        client.try_approve_artisan(&ghost, &finder),
+        Err(Ok(RegistryError::CallerNotRegistered))
+    );
+}
+
 #[test]
 fn test_blacklisted_user_state_persisted() {
     let (env, contract_id, client) = setup_env();
@@ -347,7 +435,7 @@ fn test_blacklisted_user_state_persisted() {
         write_profile(
             &env,
             &user,
-            &Profile {
+            &StoredProfile {
                 role: ROLE_ARTISAN,
                 metadata_hash: String::from_str(&env, "hash"),
                 is_verified: true,
@@ -401,7 +489,6 @@ fn test_full_lifecycle() {
 }
 
 #[test]
-#[should_panic(expected = "Caller must be Curator or Admin")]
 fn test_full_lifecycle_finder_cannot_approve() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -413,7 +500,10 @@ fn test_full_lifecycle_finder_cannot_approve() {
     seed_profile(&env, &contract_id, &finder, ROLE_FINDER);
     seed_profile(&env, &contract_id, &artisan_candidate, ROLE_FINDER);
 
-    client.approve_artisan(&finder, &artisan_candidate);
+    assert_eq!(
+        client.try_approve_artisan(&finder, &artisan_candidate),
+        Err(Ok(RegistryError::NotCuratorOrAdmin))
+    );
 }
 
 // ── transfer_admin tests ─────────────────────────────────────────────────────
@@ -434,7 +524,6 @@ fn test_transfer_admin_success() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized caller")]
 fn test_transfer_admin_wrong_caller() {
     let (env, _contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -443,11 +532,13 @@ fn test_transfer_admin_wrong_caller() {
     env.mock_all_auths();
 
     client.initialize(&admin);
-    client.transfer_admin(&impostor, &new_admin);
+    assert_eq!(
+        client.try_transfer_admin(&impostor, &new_admin),
+        Err(Ok(RegistryError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "No current admin")]
 fn test_transfer_admin_not_initialized() {
     let (env, _contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -455,11 +546,13 @@ fn test_transfer_admin_not_initialized() {
     env.mock_all_auths();
 
     // No initialize() call — should panic
-    client.transfer_admin(&admin, &new_admin);
+    assert_eq!(
+        client.try_transfer_admin(&admin, &new_admin),
+        Err(Ok(RegistryError::NotInitialized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized caller")]
 fn test_transfer_admin_old_admin_cannot_transfer_again() {
     let (env, _contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -470,7 +563,10 @@ fn test_transfer_admin_old_admin_cannot_transfer_again() {
     client.transfer_admin(&admin, &new_admin);
 
     // old admin tries to reclaim — must fail
-    client.transfer_admin(&admin, &admin);
+    assert_eq!(
+        client.try_transfer_admin(&admin, &admin),
+        Err(Ok(RegistryError::Unauthorized))
+    );
 }
 
 #[test]
@@ -569,7 +665,7 @@ fn test_blacklist_user_preserves_role_and_verification() {
         write_profile(
             &env,
             &artisan,
-            &Profile {
+            &StoredProfile {
                 role: ROLE_ARTISAN,
                 metadata_hash: String::from_str(&env, "artisan_metadata"),
                 is_verified: true,
@@ -626,7 +722,6 @@ fn test_blacklist_user_by_new_admin_after_transfer() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized caller")]
 fn test_blacklist_user_rejects_non_admin_caller() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -638,11 +733,13 @@ fn test_blacklist_user_rejects_non_admin_caller() {
     seed_profile(&env, &contract_id, &curator, ROLE_CURATOR);
     seed_profile(&env, &contract_id, &user, ROLE_FINDER);
 
-    client.blacklist_user(&curator, &user);
+    assert_eq!(
+        client.try_blacklist_user(&curator, &user),
+        Err(Ok(RegistryError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized caller")]
 fn test_unblacklist_user_rejects_non_admin_caller() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -655,11 +752,13 @@ fn test_unblacklist_user_rejects_non_admin_caller() {
     seed_profile(&env, &contract_id, &user, ROLE_FINDER);
     client.blacklist_user(&admin, &user);
 
-    client.unblacklist_user(&curator, &user);
+    assert_eq!(
+        client.try_unblacklist_user(&curator, &user),
+        Err(Ok(RegistryError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized caller")]
 fn test_blacklist_user_rejects_old_admin_after_transfer() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -671,7 +770,10 @@ fn test_blacklist_user_rejects_old_admin_after_transfer() {
     seed_profile(&env, &contract_id, &user, ROLE_FINDER);
     client.transfer_admin(&admin, &new_admin);
 
-    client.blacklist_user(&admin, &user);
+    assert_eq!(
+        client.try_blacklist_user(&admin, &user),
+        Err(Ok(RegistryError::Unauthorized))
+    );
 }
 
 #[test]
@@ -706,7 +808,6 @@ fn test_unblacklist_user_requires_admin_signature() {
 }
 
 #[test]
-#[should_panic(expected = "Contract not initialized")]
 fn test_blacklist_user_requires_initialization() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -715,11 +816,13 @@ fn test_blacklist_user_requires_initialization() {
 
     seed_profile(&env, &contract_id, &user, ROLE_FINDER);
 
-    client.blacklist_user(&admin, &user);
+    assert_eq!(
+        client.try_blacklist_user(&admin, &user),
+        Err(Ok(RegistryError::NotInitialized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "User not found")]
 fn test_blacklist_user_panics_for_unregistered_user() {
     let (env, _contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -728,11 +831,13 @@ fn test_blacklist_user_panics_for_unregistered_user() {
 
     client.initialize(&admin);
 
-    client.blacklist_user(&admin, &ghost);
+    assert_eq!(
+        client.try_blacklist_user(&admin, &ghost),
+        Err(Ok(RegistryError::UserNotFound))
+    );
 }
 
 #[test]
-#[should_panic(expected = "User not found")]
 fn test_unblacklist_user_panics_for_unregistered_user() {
     let (env, _contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -741,11 +846,13 @@ fn test_unblacklist_user_panics_for_unregistered_user() {
 
     client.initialize(&admin);
 
-    client.unblacklist_user(&admin, &ghost);
+    assert_eq!(
+        client.try_unblacklist_user(&admin, &ghost),
+        Err(Ok(RegistryError::UserNotFound))
+    );
 }
 
 #[test]
-#[should_panic(expected = "User is already blacklisted")]
 fn test_blacklist_user_twice_fails() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -756,11 +863,13 @@ fn test_blacklist_user_twice_fails() {
     seed_profile(&env, &contract_id, &user, ROLE_FINDER);
 
     client.blacklist_user(&admin, &user);
-    client.blacklist_user(&admin, &user);
+    assert_eq!(
+        client.try_blacklist_user(&admin, &user),
+        Err(Ok(RegistryError::AlreadyBlacklisted))
+    );
 }
 
 #[test]
-#[should_panic(expected = "User is not blacklisted")]
 fn test_unblacklist_user_when_not_blacklisted_fails() {
     let (env, contract_id, client) = setup_env();
     let admin = Address::generate(&env);
@@ -770,5 +879,162 @@ fn test_unblacklist_user_when_not_blacklisted_fails() {
     client.initialize(&admin);
     seed_profile(&env, &contract_id, &user, ROLE_FINDER);
 
-    client.unblacklist_user(&admin, &user);
+    assert_eq!(
+        client.try_unblacklist_user(&admin, &user),
+        Err(Ok(RegistryError::NotBlacklisted))
+    );
+}
+
+// ── market reputation tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_authorize_and_revoke_market() {
+    let (env, contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let market = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    assert!(!client.is_market_authorized(&market));
+
+    client.authorize_market(&admin, &market);
+    assert_last_event(&env, &contract_id, "market_authorized", &market);
+    assert!(client.is_market_authorized(&market));
+
+    client.revoke_market(&admin, &market);
+    assert_last_event(&env, &contract_id, "market_revoked", &market);
+    assert!(!client.is_market_authorized(&market));
+
+    assert_eq!(
+        client.try_revoke_market(&admin, &market),
+        Err(Ok(RegistryError::MarketNotAuthorized))
+    );
+}
+
+#[test]
+fn test_authorize_market_rejects_non_admin_caller() {
+    let (env, _contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let impostor = Address::generate(&env);
+    let market = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+
+    assert_eq!(
+        client.try_authorize_market(&impostor, &market),
+        Err(Ok(RegistryError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_update_reputation_by_authorized_market() {
+    let (env, contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let market = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    client.authorize_market(&admin, &market);
+    seed_profile(&env, &contract_id, &user, ROLE_ARTISAN);
+
+    client.update_reputation(&market, &user, &ReputationUpdate::JobCompleted);
+    client.update_reputation(&market, &user, &ReputationUpdate::DisputeWon);
+    client.update_reputation(&market, &user, &ReputationUpdate::DisputeLost);
+    client.update_reputation(&market, &user, &ReputationUpdate::JobAbandoned);
+    client.update_reputation(&market, &user, &ReputationUpdate::Rated(5));
+    client.update_reputation(&market, &user, &ReputationUpdate::Rated(4));
+    assert_last_event(&env, &contract_id, "reputation_updated", &user);
+
+    let profile = client.get_profile(&user);
+    assert_eq!(profile.role, ROLE_ARTISAN);
+    assert_eq!(
+        profile.reputation,
+        ReputationStats {
+            completed_jobs: 1,
+            disputes_won: 1,
+            disputes_lost: 1,
+            jobs_abandoned: 1,
+            rating_count: 2,
+            rating_total: 9,
+            average_rating: 450,
+        }
+    );
+}
+
+#[test]
+fn test_update_reputation_leaves_stored_profile_layout() {
+    let (env, contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let market = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    client.authorize_market(&admin, &market);
+    // Profiles written before reputation tracking hold only these fields
+    seed_profile(&env, &contract_id, &user, ROLE_ARTISAN);
+
+    client.update_reputation(&market, &user, &ReputationUpdate::JobCompleted);
+
+    let stored = env.as_contract(&contract_id, || read_profile(&env, &user));
+    assert_eq!(stored.map(|profile| profile.role), Some(ROLE_ARTISAN));
+    assert_eq!(client.get_profile(&user).reputation.completed_jobs, 1);
+}
+
+#[test]
+fn test_update_reputation_rejects_unauthorized_market() {
+    let (env, contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let market = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    seed_profile(&env, &contract_id, &user, ROLE_ARTISAN);
+
+    assert_eq!(
+        client.try_update_reputation(&market, &user, &ReputationUpdate::JobCompleted),
+        Err(Ok(RegistryError::MarketNotAuthorized))
+    );
+    assert_eq!(
+        client.get_profile(&user).reputation,
+        ReputationStats::default()
+    );
+}
+
+#[test]
+fn test_update_reputation_rejects_invalid_rating() {
+    let (env, contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let market = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    client.authorize_market(&admin, &market);
+    seed_profile(&env, &contract_id, &user, ROLE_ARTISAN);
+
+    assert_eq!(
+        client.try_update_reputation(&market, &user, &ReputationUpdate::Rated(6)),
+        Err(Ok(RegistryError::InvalidRating))
+    );
+}
+
+#[test]
+fn test_update_reputation_unregistered_user_fails() {
+    let (env, _contract_id, client) = setup_env();
+    let admin = Address::generate(&env);
+    let market = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    client.authorize_market(&admin, &market);
+
+    assert_eq!(
+        client.try_update_reputation(&market, &user, &ReputationUpdate::JobCompleted),
+        Err(Ok(RegistryError::UserNotFound))
+    );
 }